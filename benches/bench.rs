@@ -105,7 +105,7 @@ impl<T: BitOper> BitArrayOper<T> for [T] {
 
 extern crate test;
 
-use test::Bencher;
+use test::{black_box, Bencher};
 
 const LEN: usize = 256;
 
@@ -121,6 +121,62 @@ fn get_bitfield<T: BitField>(v: &Vec<T>) {
     }
 }
 
+fn get_bits_checked<T: BitField>(v: &[T]) {
+    for i in 0..(v.len() - 1) * T::BIT_LENGTH {
+        black_box(v.get_bits(i..i + 5));
+    }
+}
+
+fn get_bits_unchecked<T: BitField>(v: &[T]) {
+    for i in 0..(v.len() - 1) * T::BIT_LENGTH {
+        black_box(unsafe { v.get_bits_unchecked(i..i + 5) });
+    }
+}
+
+fn set_bits_checked<T: BitField, F: Fn() -> T>(v: &mut [T], value: F) {
+    for i in 0..(v.len() - 1) * T::BIT_LENGTH {
+        v.set_bits(i..i + 5, value());
+    }
+}
+
+fn set_bits_unchecked<T: BitField, F: Fn() -> T>(v: &mut [T], value: F) {
+    for i in 0..(v.len() - 1) * T::BIT_LENGTH {
+        unsafe { v.set_bits_unchecked(i..i + 5, value()) };
+    }
+}
+
+fn get_field_bits_checked<T: BitField>(v: &[T]) {
+    for x in v {
+        for i in 0..T::BIT_LENGTH - 5 {
+            black_box(x.get_bits(i..i + 5));
+        }
+    }
+}
+
+fn get_field_bits_unchecked<T: BitField>(v: &[T]) {
+    for x in v {
+        for i in 0..T::BIT_LENGTH - 5 {
+            black_box(unsafe { x.get_bits_unchecked(i..i + 5) });
+        }
+    }
+}
+
+fn set_field_bits_checked<T: BitField, F: Fn() -> T>(v: &mut [T], value: F) {
+    for x in v {
+        for i in 0..T::BIT_LENGTH - 5 {
+            x.set_bits(i..i + 5, value());
+        }
+    }
+}
+
+fn set_field_bits_unchecked<T: BitField, F: Fn() -> T>(v: &mut [T], value: F) {
+    for x in v {
+        for i in 0..T::BIT_LENGTH - 5 {
+            unsafe { x.set_bits_unchecked(i..i + 5, value()) };
+        }
+    }
+}
+
 fn set_trivial<T: BitOper>(v: &mut Vec<T>) {
     for i in 0..v.len() * T::BIT_LEN {
         v.set_b(i, true);
@@ -231,3 +287,131 @@ fn u64_get_trivial(b: &mut Bencher) {
         get_trivial(&v);
     });
 }
+
+#[bench]
+fn u32_get_bits_checked(b: &mut Bencher) {
+    let v = vec![0x5a5a_5a5au32; LEN];
+    b.iter(|| {
+        get_bits_checked(&v);
+    });
+}
+
+#[bench]
+fn u32_get_bits_unchecked(b: &mut Bencher) {
+    let v = vec![0x5a5a_5a5au32; LEN];
+    b.iter(|| {
+        get_bits_unchecked(&v);
+    });
+}
+
+#[bench]
+fn u32_set_bits_checked(b: &mut Bencher) {
+    let mut v = vec![0u32; LEN];
+    b.iter(|| {
+        set_bits_checked(&mut v, || black_box(0b10110));
+    });
+}
+
+#[bench]
+fn u32_set_bits_unchecked(b: &mut Bencher) {
+    let mut v = vec![0u32; LEN];
+    b.iter(|| {
+        set_bits_unchecked(&mut v, || black_box(0b10110));
+    });
+}
+
+#[bench]
+fn u64_get_bits_checked(b: &mut Bencher) {
+    let v = vec![0x5a5a_5a5a_5a5a_5a5au64; LEN];
+    b.iter(|| {
+        get_bits_checked(&v);
+    });
+}
+
+#[bench]
+fn u64_get_bits_unchecked(b: &mut Bencher) {
+    let v = vec![0x5a5a_5a5a_5a5a_5a5au64; LEN];
+    b.iter(|| {
+        get_bits_unchecked(&v);
+    });
+}
+
+#[bench]
+fn u64_set_bits_checked(b: &mut Bencher) {
+    let mut v = vec![0u64; LEN];
+    b.iter(|| {
+        set_bits_checked(&mut v, || black_box(0b10110));
+    });
+}
+
+#[bench]
+fn u64_set_bits_unchecked(b: &mut Bencher) {
+    let mut v = vec![0u64; LEN];
+    b.iter(|| {
+        set_bits_unchecked(&mut v, || black_box(0b10110));
+    });
+}
+
+#[bench]
+fn u32_field_get_bits_checked(b: &mut Bencher) {
+    let v = vec![0x5a5a_5a5au32; LEN];
+    b.iter(|| {
+        get_field_bits_checked(&v);
+    });
+}
+
+#[bench]
+fn u32_field_get_bits_unchecked(b: &mut Bencher) {
+    let v = vec![0x5a5a_5a5au32; LEN];
+    b.iter(|| {
+        get_field_bits_unchecked(&v);
+    });
+}
+
+#[bench]
+fn u32_field_set_bits_checked(b: &mut Bencher) {
+    let mut v = vec![0u32; LEN];
+    b.iter(|| {
+        set_field_bits_checked(&mut v, || black_box(0b10110));
+    });
+}
+
+#[bench]
+fn u32_field_set_bits_unchecked(b: &mut Bencher) {
+    let mut v = vec![0u32; LEN];
+    b.iter(|| {
+        set_field_bits_unchecked(&mut v, || black_box(0b10110));
+    });
+}
+
+#[bench]
+fn u64_field_get_bits_checked(b: &mut Bencher) {
+    let v = vec![0x5a5a_5a5a_5a5a_5a5au64; LEN];
+    b.iter(|| {
+        get_field_bits_checked(&v);
+    });
+}
+
+#[bench]
+fn u64_field_get_bits_unchecked(b: &mut Bencher) {
+    let v = vec![0x5a5a_5a5a_5a5a_5a5au64; LEN];
+    b.iter(|| {
+        get_field_bits_unchecked(&v);
+    });
+}
+
+#[bench]
+fn u64_field_set_bits_checked(b: &mut Bencher) {
+    let mut v = vec![0u64; LEN];
+    b.iter(|| {
+        set_field_bits_checked(&mut v, || black_box(0b10110));
+    });
+}
+
+#[bench]
+fn u64_field_set_bits_unchecked(b: &mut Bencher) {
+    let mut v = vec![0u64; LEN];
+    b.iter(|| {
+        set_field_bits_unchecked(&mut v, || black_box(0b10110));
+    });
+}
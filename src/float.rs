@@ -0,0 +1,234 @@
+use core::ops::{Range, RangeBounds};
+
+use BitField;
+
+/// A companion trait to `BitField` for the IEEE-754 floating point types, which operates on the
+/// raw bit pattern of the value as returned by `to_bits`.
+pub trait FloatBitField {
+    /// The unsigned integer type holding the bit pattern of this float type.
+    type Bits: BitField;
+
+    /// The index of the sign bit.
+    const SIGN_BIT: usize;
+
+    /// The range of the biased exponent bits.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// assert_eq!(f32::EXPONENT_RANGE, 23..31);
+    /// assert_eq!(f64::EXPONENT_RANGE, 52..63);
+    /// ```
+    const EXPONENT_RANGE: Range<usize>;
+
+    /// The range of the fraction (also called mantissa or significand) bits.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// assert_eq!(f32::FRACTION_RANGE, 0..23);
+    /// assert_eq!(f64::FRACTION_RANGE, 0..52);
+    /// ```
+    const FRACTION_RANGE: Range<usize>;
+
+    /// Obtains the bit at the index `bit` of the bit pattern; note that index 0 is the least
+    /// significant bit of the fraction, while the sign is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// assert_eq!((-1.0f32).get_bit(31), true);
+    /// assert_eq!(1.0f32.get_bit(23), true);
+    /// assert_eq!(1.0f32.get_bit(22), false);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit pattern.
+    fn get_bit(&self, bit: usize) -> bool;
+
+    /// Obtains the range of bits specified by `range` of the bit pattern.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// assert_eq!(1.5f32.get_bits(21..31), 0b0111_1111_10);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit pattern.
+    fn get_bits<T: RangeBounds<usize>>(&self, range: T) -> Self::Bits;
+
+    /// Sets the bit at the index `bit` of the bit pattern to the value `value`.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// let mut value = 2.0f64;
+    ///
+    /// value.set_bit(63, true);
+    /// assert_eq!(value, -2.0);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of the bounds of the bit pattern.
+    fn set_bit(&mut self, bit: usize, value: bool) -> &mut Self;
+
+    /// Sets the range of bits defined by the range `range` of the bit pattern to the lower bits
+    /// of `value`.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// let mut value = 1.0f32;
+    ///
+    /// value.set_bits(22..23, 1);
+    /// assert_eq!(value, 1.5);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit pattern, or if there are
+    /// `1`s not in the lower N bits of `value`.
+    fn set_bits<T: RangeBounds<usize>>(&mut self, range: T, value: Self::Bits) -> &mut Self;
+
+    /// Returns the sign bit, which is `true` for negative values (including `-0.0` and NaNs with
+    /// the sign bit set).
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// assert_eq!((-0.0f32).sign(), true);
+    /// assert_eq!(0.0f32.sign(), false);
+    /// ```
+    #[inline]
+    fn sign(&self) -> bool {
+        self.get_bit(Self::SIGN_BIT)
+    }
+
+    /// Returns the biased exponent bits.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// assert_eq!(1.0f32.exponent(), 127);
+    /// assert_eq!(f64::MIN_POSITIVE.exponent(), 1);
+    /// ```
+    #[inline]
+    fn exponent(&self) -> Self::Bits {
+        self.get_bits(Self::EXPONENT_RANGE)
+    }
+
+    /// Returns the fraction bits, without the implicit leading bit.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// assert_eq!(1.75f32.fraction(), 0b11 << 21);
+    /// ```
+    #[inline]
+    fn fraction(&self) -> Self::Bits {
+        self.get_bits(Self::FRACTION_RANGE)
+    }
+
+    /// Sets the sign bit.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// let mut value = 3.0f32;
+    ///
+    /// value.set_sign(true);
+    /// assert_eq!(value, -3.0);
+    /// ```
+    #[inline]
+    fn set_sign(&mut self, value: bool) -> &mut Self {
+        self.set_bit(Self::SIGN_BIT, value)
+    }
+
+    /// Sets the biased exponent bits.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// let mut value = 1.0f32;
+    ///
+    /// value.set_exponent(130);
+    /// assert_eq!(value, 8.0);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `value` does not fit into the exponent bits.
+    #[inline]
+    fn set_exponent(&mut self, value: Self::Bits) -> &mut Self {
+        self.set_bits(Self::EXPONENT_RANGE, value)
+    }
+
+    /// Sets the fraction bits.
+    ///
+    /// ```rust
+    /// use bit_field::FloatBitField;
+    ///
+    /// let mut value = f64::NAN;
+    ///
+    /// value.set_fraction(0x42);
+    /// assert!(value.is_nan());
+    /// assert_eq!(value.fraction(), 0x42);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `value` does not fit into the fraction bits.
+    #[inline]
+    fn set_fraction(&mut self, value: Self::Bits) -> &mut Self {
+        self.set_bits(Self::FRACTION_RANGE, value)
+    }
+}
+
+/// An internal macro used for implementing FloatBitField on the standard floating point types.
+macro_rules! float_bitfield_impl {
+    ($($t:ty => $bits:ty, $mantissa_digits:expr;)*) => ($(
+        impl FloatBitField for $t {
+            type Bits = $bits;
+
+            const SIGN_BIT: usize = <$bits as BitField>::BIT_LENGTH - 1;
+            const EXPONENT_RANGE: Range<usize> = ($mantissa_digits - 1)..Self::SIGN_BIT;
+            const FRACTION_RANGE: Range<usize> = 0..($mantissa_digits - 1);
+
+            #[track_caller]
+            #[inline]
+            fn get_bit(&self, bit: usize) -> bool {
+                self.to_bits().get_bit(bit)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn get_bits<T: RangeBounds<usize>>(&self, range: T) -> $bits {
+                self.to_bits().get_bits(range)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bit(&mut self, bit: usize, value: bool) -> &mut Self {
+                *self = <$t>::from_bits(*self.to_bits().set_bit(bit, value));
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bits<T: RangeBounds<usize>>(&mut self, range: T, value: $bits) -> &mut Self {
+                *self = <$t>::from_bits(*self.to_bits().set_bits(range, value));
+                self
+            }
+        }
+    )*)
+}
+
+float_bitfield_impl! {
+    f32 => u32, f32::MANTISSA_DIGITS as usize;
+    f64 => u64, f64::MANTISSA_DIGITS as usize;
+}
@@ -0,0 +1,174 @@
+use core::ops::RangeBounds;
+
+use mask::RangeMask;
+use {to_regular_range, BitField};
+
+/// Conversion between binary numbers and the reflected binary Gray code, in which consecutive
+/// numbers differ in exactly one bit.
+///
+/// Signed types are treated as their bit pattern, i.e. like the unsigned type of the same width.
+pub trait GrayCode: BitField + Sized {
+    /// Returns the Gray code of `self`.
+    ///
+    /// ```rust
+    /// use bit_field::GrayCode;
+    ///
+    /// assert_eq!(0b0111u8.to_gray(), 0b0100);
+    /// assert_eq!(0b1000u8.to_gray(), 0b1100);
+    /// ```
+    fn to_gray(self) -> Self;
+
+    /// Returns the binary number whose Gray code is `self`; this is the inverse of `to_gray`.
+    ///
+    /// ```rust
+    /// use bit_field::GrayCode;
+    ///
+    /// assert_eq!(0b1100u8.from_gray(), 0b1000);
+    /// assert_eq!(u32::MAX.to_gray().from_gray(), u32::MAX);
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_gray(self) -> Self;
+
+    /// Replaces the range of bits specified by `range` with its Gray code, treating the bits as an
+    /// unsigned integer; all bits outside of the range are left untouched.
+    ///
+    /// ```rust
+    /// use bit_field::GrayCode;
+    ///
+    /// let mut value: u16 = 0xf_7_ff;
+    ///
+    /// value.to_gray_in(8..12);
+    /// assert_eq!(value, 0xf_4_ff);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field.
+    fn to_gray_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self;
+
+    /// Replaces the range of bits specified by `range`, which holds a Gray code, with the binary
+    /// number it encodes; all bits outside of the range are left untouched.
+    ///
+    /// ```rust
+    /// use bit_field::GrayCode;
+    ///
+    /// let mut value: u16 = 0xf_4_ff;
+    ///
+    /// value.from_gray_in(8..12);
+    /// assert_eq!(value, 0xf_7_ff);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_gray_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self;
+}
+
+/// An internal macro used for implementing GrayCode on the standard integral types.
+macro_rules! gray_code_impl {
+    ($($t:ty)*) => ($(
+        impl GrayCode for $t {
+            #[inline]
+            fn to_gray(self) -> Self {
+                // mask the shifted value, so that the shift is a logical one for signed types
+                self ^ ((self >> 1) & Self::range_mask(0..Self::BIT_LENGTH - 1))
+            }
+
+            #[inline]
+            fn from_gray(self) -> Self {
+                // every bit is the XOR of itself and all more significant bits of the Gray code,
+                // which doubling shifts compute in log2(BIT_LENGTH) steps
+                let mut value = self;
+                let mut shift = 1;
+                while shift < Self::BIT_LENGTH {
+                    value ^= (value >> shift) & Self::range_mask(0..Self::BIT_LENGTH - shift);
+                    shift <<= 1;
+                }
+                value
+            }
+
+            #[track_caller]
+            #[inline]
+            fn to_gray_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                let mask = Self::range_mask(range.clone());
+                let field = ((*self & mask) >> range.start) & Self::range_mask(0..range.len());
+
+                *self = (*self & !mask) | (field.to_gray() << range.start);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn from_gray_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                let mask = Self::range_mask(range.clone());
+                let field = ((*self & mask) >> range.start) & Self::range_mask(0..range.len());
+
+                *self = (*self & !mask) | (field.from_gray() << range.start);
+                self
+            }
+        }
+
+        impl GrayCodes<$t> {
+            /// Creates an iterator over all `bits`-bit values in Gray code order, starting at `0`.
+            ///
+            /// ## Panics
+            ///
+            /// This function will panic if `bits` is larger than the bit length of the type.
+            #[track_caller]
+            #[inline]
+            pub fn new(bits: usize) -> Self {
+                assert!(bits <= <$t>::BIT_LENGTH);
+
+                GrayCodes {
+                    next: Some(0),
+                    last: <$t>::range_mask(0..bits),
+                }
+            }
+        }
+
+        impl Iterator for GrayCodes<$t> {
+            type Item = $t;
+
+            #[inline]
+            fn next(&mut self) -> Option<$t> {
+                let index = self.next?;
+                self.next = if index == self.last {
+                    None
+                } else {
+                    Some(index.wrapping_add(1))
+                };
+                Some(index.to_gray())
+            }
+        }
+    )*)
+}
+
+/// An iterator over all values of a given number of bits in Gray code order, in which each value
+/// differs from the previous one in exactly one bit.
+///
+/// ```rust
+/// use bit_field::GrayCodes;
+///
+/// assert!(GrayCodes::<u8>::new(3).eq([0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100]));
+/// assert_eq!(GrayCodes::<u16>::new(16).count(), 1 << 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrayCodes<T> {
+    next: Option<T>,
+    last: T,
+}
+
+gray_code_impl! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
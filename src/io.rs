@@ -0,0 +1,435 @@
+//! Bit stream adapters over `std::io::Read` and `std::io::Write`.
+//!
+//! Bits are read and written least significant bit first, which is the same order `BitArray`
+//! uses for a `[u8]` slice: bit `i` of the stream is bit `i % 8` of byte `i / 8`.
+
+use std::io::{self, Read, Write};
+use std::mem::ManuallyDrop;
+use std::ptr;
+
+use {BitArray, BitField};
+
+/// The number of bytes buffered by `BitReader` and `BitWriter`.
+const BUFFER_SIZE: usize = 64;
+
+/// Reads individual bits and bit groups from an underlying reader.
+///
+/// ```rust
+/// use bit_field::BitReader;
+///
+/// let mut reader = BitReader::new(&[0b1010_0110u8, 0xff][..]);
+///
+/// assert_eq!(reader.read_bit().unwrap(), false);
+/// assert_eq!(reader.read_bits(3).unwrap(), 0b011);
+/// assert_eq!(reader.read_bits(8).unwrap(), 0b1111_1010);
+/// ```
+pub struct BitReader<R: Read> {
+    inner: R,
+    buf: [u8; BUFFER_SIZE],
+    /// The number of valid bytes in `buf`.
+    len: usize,
+    /// The index of the next bit to read from `buf`.
+    pos: usize,
+}
+
+impl<R: Read> BitReader<R> {
+    /// Creates a new bit reader reading from `inner`.
+    pub fn new(inner: R) -> BitReader<R> {
+        BitReader {
+            inner,
+            buf: [0; BUFFER_SIZE],
+            len: 0,
+            pos: 0,
+        }
+    }
+
+    /// Reads a single bit.
+    ///
+    /// Returns an error of kind `UnexpectedEof` if the underlying reader is exhausted.
+    pub fn read_bit(&mut self) -> io::Result<bool> {
+        self.fill_buf()?;
+        let bit = self.buf.get_bit(self.pos);
+        self.pos += 1;
+        Ok(bit)
+    }
+
+    /// Reads `count` bits and returns them in the lower bits of the result, the first bit read
+    /// being the least significant one.
+    ///
+    /// Returns an error of kind `UnexpectedEof` if the underlying reader is exhausted; the bits
+    /// that were already consumed by this call are lost in that case.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `count` is larger than 64.
+    pub fn read_bits(&mut self, count: usize) -> io::Result<u64> {
+        assert!(count <= u64::BIT_LENGTH);
+
+        let mut value = 0u64;
+        let mut done = 0;
+        while done < count {
+            self.fill_buf()?;
+            let len = (count - done).min(8 - self.pos % 8);
+            let bits = self.buf.get_bits(self.pos..self.pos + len);
+            value.set_bits(done..done + len, u64::from(bits));
+            self.pos += len;
+            done += len;
+        }
+        Ok(value)
+    }
+
+    /// Reads a unary code, i.e. a number of `0` bits terminated by a `1` bit, and returns the
+    /// number of `0` bits.
+    ///
+    /// Returns an error of kind `UnexpectedEof` if the underlying reader is exhausted.
+    pub fn read_unary(&mut self) -> io::Result<u64> {
+        self.read_zeros(u64::MAX)
+    }
+
+    /// Reads an unsigned Exp-Golomb code (`ue(v)`): a unary prefix of `n` `0` bits and a `1` bit,
+    /// followed by an `n` bit suffix that is read like `read_bits`; the value is
+    /// `2^n - 1 + suffix`.
+    ///
+    /// ```rust
+    /// use bit_field::BitReader;
+    ///
+    /// let mut reader = BitReader::new(&[0b0110_0101u8][..]);
+    ///
+    /// assert_eq!(reader.read_ue().unwrap(), 0);
+    /// assert_eq!(reader.read_ue().unwrap(), 1);
+    /// assert_eq!(reader.read_ue().unwrap(), 2);
+    /// ```
+    ///
+    /// Returns an error of kind `InvalidData` if the prefix is longer than 64 bits or the value
+    /// does not fit into a `u64`, and an error of kind `UnexpectedEof` if the underlying reader is
+    /// exhausted. The bits that were already consumed by this call are lost in both cases.
+    pub fn read_ue(&mut self) -> io::Result<u64> {
+        let len = self.read_zeros(u64::BIT_LENGTH as u64)? as usize;
+        let suffix = self.read_bits(len)?;
+        let base = ((1u128 << len) - 1) as u64;
+        base.checked_add(suffix)
+            .ok_or_else(|| invalid_data("Exp-Golomb code does not fit into a u64"))
+    }
+
+    /// Reads a signed Exp-Golomb code (`se(v)`), which maps the unsigned codes 0, 1, 2, 3, 4, ...
+    /// to the values 0, 1, -1, 2, -2, ...
+    ///
+    /// Returns an error of kind `InvalidData` if the code is malformed or the value does not fit
+    /// into an `i64`, and an error of kind `UnexpectedEof` if the underlying reader is exhausted.
+    pub fn read_se(&mut self) -> io::Result<i64> {
+        let code = self.read_ue()?;
+        if code == u64::MAX {
+            return Err(invalid_data("Exp-Golomb code does not fit into an i64"));
+        }
+        let magnitude = (code / 2 + code % 2) as i64;
+        Ok(if code % 2 == 1 { magnitude } else { -magnitude })
+    }
+
+    /// Reads a Golomb-Rice code with parameter `k`: the quotient `value >> k` as a unary code,
+    /// followed by the lower `k` bits of the value, which are read like `read_bits`.
+    ///
+    /// Returns an error of kind `InvalidData` if the value does not fit into a `u64`, and an error
+    /// of kind `UnexpectedEof` if the underlying reader is exhausted.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `k` is 64 or larger.
+    pub fn read_rice(&mut self, k: usize) -> io::Result<u64> {
+        assert!(k < u64::BIT_LENGTH);
+
+        let quotient = self.read_zeros(u64::MAX >> k)?;
+        let remainder = self.read_bits(k)?;
+        Ok(quotient << k | remainder)
+    }
+
+    /// Skips the remaining bits of the current byte, so that the next read starts at a byte
+    /// boundary.
+    pub fn align(&mut self) {
+        self.pos = self.pos.next_multiple_of(8);
+    }
+
+    /// Returns whether the reader is positioned at a byte boundary.
+    pub fn is_aligned(&self) -> bool {
+        self.pos % 8 == 0
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader skips over the data buffered by this reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `BitReader`, returning the underlying reader.
+    ///
+    /// Any buffered data that was not read yet is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads `0` bits up to and including the next `1` bit and returns the number of `0` bits, or
+    /// an error of kind `InvalidData` if there are more than `limit` of them.
+    fn read_zeros(&mut self, limit: u64) -> io::Result<u64> {
+        let mut count = 0u64;
+        loop {
+            self.fill_buf()?;
+            let end = self.len * 8;
+            let one = self.buf[..self.len].lowest_set_bit_in(self.pos..end);
+            let zeros = one.unwrap_or(end) - self.pos;
+            count = count
+                .checked_add(zeros as u64)
+                .filter(|&count| count <= limit)
+                .ok_or_else(|| invalid_data("unary prefix is too long"))?;
+            self.pos += zeros;
+            if one.is_some() {
+                self.pos += 1;
+                return Ok(count);
+            }
+        }
+    }
+
+    /// Ensures that at least one unread bit is available in `buf`.
+    fn fill_buf(&mut self) -> io::Result<()> {
+        if self.pos < self.len * 8 {
+            return Ok(());
+        }
+        loop {
+            match self.inner.read(&mut self.buf) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+                Ok(len) => {
+                    self.len = len;
+                    self.pos = 0;
+                    return Ok(());
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Writes individual bits and bit groups to an underlying writer.
+///
+/// Bits are collected in an internal buffer and only written out in whole bytes. Call `flush`
+/// to write out all complete bytes, or `into_inner` to pad the last byte with zeros and write out
+/// everything. Dropping the writer also pads and writes out the buffered bits, but ignores any
+/// errors.
+///
+/// ```rust
+/// use bit_field::BitWriter;
+///
+/// let mut writer = BitWriter::new(Vec::new());
+///
+/// writer.write_bit(false).unwrap();
+/// writer.write_bits(3, 0b011).unwrap();
+/// writer.write_bits(8, 0b1111_1010).unwrap();
+///
+/// assert_eq!(writer.into_inner().unwrap(), [0b1010_0110, 0b1111]);
+/// ```
+pub struct BitWriter<W: Write> {
+    inner: W,
+    buf: [u8; BUFFER_SIZE],
+    /// The number of bits written to `buf`.
+    pos: usize,
+    /// Whether the underlying writer panicked while writing out `buf`.
+    panicked: bool,
+}
+
+impl<W: Write> BitWriter<W> {
+    /// Creates a new bit writer writing to `inner`.
+    pub fn new(inner: W) -> BitWriter<W> {
+        BitWriter {
+            inner,
+            buf: [0; BUFFER_SIZE],
+            pos: 0,
+            panicked: false,
+        }
+    }
+
+    /// Writes a single bit.
+    pub fn write_bit(&mut self, value: bool) -> io::Result<()> {
+        self.make_room()?;
+        self.buf.set_bit(self.pos, value);
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Writes the lower `count` bits of `value`, least significant bit first.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `count` is larger than 64, or if there are `1`s not in the lower
+    /// `count` bits of `value`.
+    pub fn write_bits(&mut self, count: usize, value: u64) -> io::Result<()> {
+        assert!(count <= u64::BIT_LENGTH);
+        assert!(
+            count == u64::BIT_LENGTH || value >> count == 0,
+            "value does not fit into bit count"
+        );
+
+        let mut done = 0;
+        while done < count {
+            self.make_room()?;
+            let len = (count - done).min(8 - self.pos % 8);
+            let bits = value.get_bits(done..done + len) as u8;
+            self.buf.set_bits(self.pos..self.pos + len, bits);
+            self.pos += len;
+            done += len;
+        }
+        Ok(())
+    }
+
+    /// Writes `value` as a unary code, i.e. `value` `0` bits followed by a `1` bit.
+    pub fn write_unary(&mut self, value: u64) -> io::Result<()> {
+        let mut zeros = value;
+        while zeros != 0 {
+            let count = zeros.min(u64::BIT_LENGTH as u64);
+            self.write_bits(count as usize, 0)?;
+            zeros -= count;
+        }
+        self.write_bit(true)
+    }
+
+    /// Writes `value` as an unsigned Exp-Golomb code (`ue(v)`), in the format read by
+    /// `BitReader::read_ue`.
+    ///
+    /// ```rust
+    /// use bit_field::BitWriter;
+    ///
+    /// let mut writer = BitWriter::new(Vec::new());
+    ///
+    /// writer.write_ue(0).unwrap();
+    /// writer.write_ue(1).unwrap();
+    /// writer.write_ue(2).unwrap();
+    ///
+    /// assert_eq!(writer.into_inner().unwrap(), [0b0110_0101]);
+    /// ```
+    pub fn write_ue(&mut self, value: u64) -> io::Result<()> {
+        let code = u128::from(value) + 1;
+        let len = code.ilog2() as usize;
+        self.write_unary(len as u64)?;
+        self.write_bits(len, (code - (1 << len)) as u64)
+    }
+
+    /// Writes `value` as a signed Exp-Golomb code (`se(v)`), in the format read by
+    /// `BitReader::read_se`.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `value` is `i64::MIN`, which has no signed Exp-Golomb code that
+    /// fits into a `u64`.
+    pub fn write_se(&mut self, value: i64) -> io::Result<()> {
+        assert!(
+            value != i64::MIN,
+            "value does not fit into an Exp-Golomb code"
+        );
+
+        let magnitude = value.unsigned_abs();
+        self.write_ue(if value > 0 {
+            magnitude * 2 - 1
+        } else {
+            magnitude * 2
+        })
+    }
+
+    /// Writes `value` as a Golomb-Rice code with parameter `k`, in the format read by
+    /// `BitReader::read_rice`.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `k` is 64 or larger.
+    pub fn write_rice(&mut self, k: usize, value: u64) -> io::Result<()> {
+        assert!(k < u64::BIT_LENGTH);
+
+        self.write_unary(value >> k)?;
+        self.write_bits(k, value.get_bits(..k))
+    }
+
+    /// Pads the current byte with zero bits, so that the next write starts at a byte boundary.
+    pub fn align(&mut self) -> io::Result<()> {
+        if !self.is_aligned() {
+            let end = self.pos.next_multiple_of(8);
+            self.buf.set_bits(self.pos..end, 0);
+            self.pos = end;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the writer is positioned at a byte boundary.
+    pub fn is_aligned(&self) -> bool {
+        self.pos % 8 == 0
+    }
+
+    /// Writes all complete buffered bytes to the underlying writer and flushes it.
+    ///
+    /// A trailing partial byte stays buffered; use `align` first to write it out as well.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_buf()?;
+        self.inner.flush()
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer bypasses the bits buffered by this writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Pads the last byte with zero bits, writes out all buffered data and returns the underlying
+    /// writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.align()?;
+        self.flush()?;
+
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so `inner` is moved out of it exactly once
+        Ok(unsafe { ptr::read(&this.inner) })
+    }
+
+    /// Ensures that at least one bit can be written to `buf`.
+    fn make_room(&mut self) -> io::Result<()> {
+        if self.pos == self.buf.bit_length() {
+            self.write_buf()?;
+        }
+        Ok(())
+    }
+
+    /// Writes all complete bytes of `buf` and moves a trailing partial byte to the front.
+    fn write_buf(&mut self) -> io::Result<()> {
+        let bytes = self.pos / 8;
+        self.panicked = true;
+        let result = self.inner.write_all(&self.buf[..bytes]);
+        self.panicked = false;
+        result?;
+        if bytes < BUFFER_SIZE {
+            self.buf[0] = self.buf[bytes];
+        }
+        self.pos %= 8;
+        Ok(())
+    }
+}
+
+/// Returns an error of kind `InvalidData` for malformed input.
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl<W: Write> Drop for BitWriter<W> {
+    fn drop(&mut self) {
+        // like `BufWriter`, don't write to an underlying writer that already panicked
+        if !self.panicked {
+            let _ = self.align();
+            let _ = self.write_buf();
+        }
+    }
+}
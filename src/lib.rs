@@ -3,11 +3,45 @@
 
 #![no_std]
 
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
 #[cfg(test)]
 mod tests;
 
+mod float;
+mod gray;
+#[cfg(feature = "std")]
+mod io;
+mod mask;
+mod matrix;
+mod morton;
+mod nonzero;
+mod permute;
+mod range;
+mod subsets;
+mod wide;
+
+pub use float::FloatBitField;
+pub use gray::{GrayCode, GrayCodes};
+#[cfg(feature = "std")]
+pub use io::{BitReader, BitWriter};
+pub use matrix::{BitMatrix, Column, Columns};
+pub use morton::{
+    deinterleave2, deinterleave3, interleave2, interleave3, Interleave2, Interleave3,
+};
+pub use nonzero::{NonZeroBitField, ZeroError};
+pub use permute::{BitPermutation, PermutationWord};
+pub use range::BitRange;
+pub use subsets::{Combinations, Submasks};
+pub use wide::Wide;
+
+use core::hint;
+use core::num::{Saturating, Wrapping};
 use core::ops::{Bound, Range, RangeBounds};
 
+use mask::{MaskOps, RangeMask};
+
 /// A generic trait which provides methods for extracting and setting specific bits or ranges of
 /// bits.
 pub trait BitField {
@@ -106,196 +140,2434 @@ pub trait BitField {
     /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
     /// not in the lower N bits of `value`.
     fn set_bits<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self;
-}
 
-pub trait BitArray<T: BitField> {
-    /// Returns the length, eg number of bits, in this bit array.
+    /// Obtains the bit at the index `bit` like `get_bit`, but without checking that the index is
+    /// in bounds in release builds.
     ///
     /// ```rust
-    /// use bit_field::BitArray;
+    /// use bit_field::BitField;
     ///
-    /// assert_eq!([0u8, 4u8, 8u8].bit_length(), 24);
-    /// assert_eq!([0u32, 5u32].bit_length(), 64);
+    /// let value: u32 = 0b110101;
+    ///
+    /// assert_eq!(unsafe { value.get_bit_unchecked(2) }, true);
     /// ```
-    fn bit_length(&self) -> usize;
+    ///
+    /// ## Safety
+    ///
+    /// `bit` must be less than `Self::BIT_LENGTH`, i.e. the call must not panic when made with
+    /// `get_bit` instead. This is only checked with `debug_assert!`.
+    #[track_caller]
+    #[inline]
+    unsafe fn get_bit_unchecked(&self, bit: usize) -> bool {
+        self.get_bit(bit)
+    }
 
-    /// Obtains the bit at the index `bit`; note that index 0 is the least significant bit, while
-    /// index `length() - 1` is the most significant bit.
+    /// Obtains the range of bits specified by `range` like `get_bits`, but without checking that
+    /// the range is in bounds in release builds.
     ///
     /// ```rust
-    /// use bit_field::BitArray;
+    /// use bit_field::BitField;
     ///
-    /// let value: [u32; 1] = [0b110101];
+    /// let value: u32 = 0b110101;
     ///
-    /// assert_eq!(value.get_bit(1), false);
-    /// assert_eq!(value.get_bit(2), true);
+    /// assert_eq!(unsafe { value.get_bits_unchecked(2..6) }, 0b1101);
     /// ```
     ///
-    /// ## Panics
+    /// ## Safety
     ///
-    /// This method will panic if the bit index is out of bounds of the bit array.
-    fn get_bit(&self, bit: usize) -> bool;
+    /// The range must be in bounds of the bit field, i.e. the call must not panic when made with
+    /// `get_bits` instead. This is only checked with `debug_assert!`.
+    #[track_caller]
+    #[inline]
+    unsafe fn get_bits_unchecked<T: RangeBounds<usize>>(&self, range: T) -> Self
+    where
+        Self: Sized,
+    {
+        self.get_bits(range)
+    }
 
-    /// Obtains the range of bits specified by `range`; note that index 0 is the least significant
-    /// bit, while index `length() - 1` is the most significant bit.
+    /// Sets the bit at the index `bit` like `set_bit`, but without checking that the index is in
+    /// bounds in release builds.
     ///
     /// ```rust
-    /// use bit_field::BitArray;
+    /// use bit_field::BitField;
     ///
-    /// let value: [u32; 2] = [0b110101, 0b11];
+    /// let mut value = 0u32;
     ///
-    /// assert_eq!(value.get_bits(0..3), 0b101);
-    /// assert_eq!(value.get_bits(..6), 0b110101);
-    /// assert_eq!(value.get_bits(31..33), 0b10);
-    /// assert_eq!(value.get_bits(5..=32), 0b1_0000_0000_0000_0000_0000_0000_001);
-    /// assert_eq!(value.get_bits(34..), 0);
+    /// unsafe { value.set_bit_unchecked(3, true) };
+    /// assert_eq!(value, 8u32);
     /// ```
     ///
-    /// ## Panics
+    /// ## Safety
     ///
-    /// This method will panic if the start or end indexes of the range are out of bounds of the
-    /// bit array, or if the range can't be contained by the bit field T.
-    fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> T;
+    /// `bit` must be less than `Self::BIT_LENGTH`, i.e. the call must not panic when made with
+    /// `set_bit` instead. This is only checked with `debug_assert!`.
+    #[track_caller]
+    #[inline]
+    unsafe fn set_bit_unchecked(&mut self, bit: usize, value: bool) -> &mut Self {
+        self.set_bit(bit, value)
+    }
 
-    /// Sets the bit at the index `bit` to the value `value` (where true means a value of '1' and
-    /// false means a value of '0'); note that index 0 is the least significant bit, while index
-    /// `length() - 1` is the most significant bit.
+    /// Sets the range of bits defined by the range `range` to the lower bits of `value` like
+    /// `set_bits`, but without checking the range and value in release builds.
     ///
     /// ```rust
-    /// use bit_field::BitArray;
+    /// use bit_field::BitField;
     ///
-    /// let mut value = [0u32];
+    /// let mut value = 0u32;
     ///
-    /// value.set_bit(1, true);
-    /// assert_eq!(value, [2u32]);
+    /// unsafe { value.set_bits_unchecked(2..=3, 0b11) };
+    /// assert_eq!(value, 0b1100);
+    /// ```
     ///
-    /// value.set_bit(3, true);
-    /// assert_eq!(value, [10u32]);
+    /// ## Safety
     ///
-    /// value.set_bit(1, false);
-    /// assert_eq!(value, [8u32]);
+    /// The range must be in bounds of the bit field and `value` must fit into it, i.e. the call
+    /// must not panic when made with `set_bits` instead. This is only checked with
+    /// `debug_assert!`.
+    #[track_caller]
+    #[inline]
+    unsafe fn set_bits_unchecked<T: RangeBounds<usize>>(
+        &mut self,
+        range: T,
+        value: Self,
+    ) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.set_bits(range, value)
+    }
+
+    /// Obtains the bits in the range `START..END`, where the range is checked at compile time
+    /// instead of at runtime.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value: u32 = 0b110101;
+    ///
+    /// assert_eq!(value.get_bits_const::<0, 3>(), 0b101);
+    /// assert_eq!(value.get_bits_const::<2, 6>(), 0b1101);
     /// ```
     ///
-    /// ## Panics
+    /// Ranges that are out of bounds or reversed fail to compile:
     ///
-    /// This method will panic if the bit index is out of the bounds of the bit array.
-    fn set_bit(&mut self, bit: usize, value: bool);
+    /// ```compile_fail
+    /// use bit_field::BitField;
+    ///
+    /// let value: u32 = 0b110101;
+    ///
+    /// value.get_bits_const::<30, 33>();
+    /// ```
+    #[track_caller]
+    #[inline]
+    fn get_bits_const<const START: usize, const END: usize>(&self) -> Self
+    where
+        Self: Sized,
+    {
+        const {
+            assert!(START <= END, "bit range is reversed");
+            assert!(START < Self::BIT_LENGTH, "bit range is out of bounds");
+            assert!(END <= Self::BIT_LENGTH, "bit range is out of bounds");
+        }
 
-    /// Sets the range of bits defined by the range `range` to the lower bits of `value`; to be
-    /// specific, if the range is N bits long, the N lower bits of `value` will be used; if any of
-    /// the other bits in `value` are set to 1, this function will panic.
+        // the range was checked above, so `get_bits` would not panic
+        unsafe { self.get_bits_unchecked(START..END) }
+    }
+
+    /// Sets the bits in the range `START..END` to the lower bits of `value`, where the range is
+    /// checked at compile time instead of at runtime.
     ///
     /// ```rust
-    /// use bit_field::BitArray;
+    /// use bit_field::BitField;
     ///
-    /// let mut value = [0u32, 0u32];
+    /// let mut value = 0u32;
     ///
-    /// value.set_bits(0..2, 0b11);
-    /// assert_eq!(value, [0b11, 0u32]);
+    /// value.set_bits_const::<2, 4>(0b11);
+    /// assert_eq!(value, 0b1100);
+    /// ```
     ///
-    /// value.set_bits(31..35, 0b1010);
-    /// assert_eq!(value, [0x0003, 0b101]);
+    /// Ranges that are out of bounds or reversed fail to compile:
+    ///
+    /// ```compile_fail
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0u32;
+    ///
+    /// value.set_bits_const::<4, 2>(0);
     /// ```
     ///
     /// ## Panics
     ///
-    /// This method will panic if the range is out of bounds of the bit array,
-    /// if the range can't be contained by the bit field T, or if there are `1`s
-    /// not in the lower N bits of `value`.
-    fn set_bits<U: RangeBounds<usize>>(&mut self, range: U, value: T);
-}
-
-/// An internal macro used for implementing BitField on the standard integral types.
-macro_rules! bitfield_numeric_impl {
-    ($($t:ty)*) => ($(
-        impl BitField for $t {
-            const BIT_LENGTH: usize = ::core::mem::size_of::<Self>() as usize * 8;
+    /// This method will panic if there are `1`s not in the lower `END - START` bits of `value`.
+    #[track_caller]
+    #[inline]
+    fn set_bits_const<const START: usize, const END: usize>(&mut self, value: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        const {
+            assert!(START <= END, "bit range is reversed");
+            assert!(START < Self::BIT_LENGTH, "bit range is out of bounds");
+            assert!(END <= Self::BIT_LENGTH, "bit range is out of bounds");
+        }
 
-            #[track_caller]
-            #[inline]
-            fn get_bit(&self, bit: usize) -> bool {
-                assert!(bit < Self::BIT_LENGTH);
+        // only the value is checked at runtime, the range checks are known to pass
+        self.set_bits(START..END, value)
+    }
 
-                (*self & (1 << bit)) != 0
+    /// Gathers the bits selected by the set bits of `mask` into the lower bits of the result,
+    /// keeping their order; this is also known as parallel bit extract (`PEXT`).
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value: u32 = 0b1011_0110;
+    ///
+    /// assert_eq!(value.extract_by_mask(0b1111_0000), 0b1011);
+    /// assert_eq!(value.extract_by_mask(0b1010_1010), 0b1101);
+    /// ```
+    #[inline]
+    fn extract_by_mask(&self, mask: Self) -> Self
+    where
+        Self: Sized,
+    {
+        // the extracted bits never move up, so they are packed into `mask` itself: each bit of
+        // the mask is read before it is overwritten
+        let mut result = mask;
+        let mut index = 0;
+        for bit in 0..Self::BIT_LENGTH {
+            if result.get_bit(bit) {
+                result.set_bit(index, self.get_bit(bit));
+                index += 1;
             }
+        }
+        for bit in index..Self::BIT_LENGTH {
+            result.set_bit(bit, false);
+        }
+        result
+    }
 
-            #[track_caller]
-            #[inline]
-            fn get_bits<T: RangeBounds<usize>>(&self, range: T) -> Self {
-                let range = to_regular_range(&range, Self::BIT_LENGTH);
-
-                assert!(range.start < Self::BIT_LENGTH);
-                assert!(range.end <= Self::BIT_LENGTH);
-                assert!(range.start <= range.end);
-
-                if range.start == range.end {
-                    0
-                } else {
-                    // shift away high bits
-                    let bits = *self << (Self::BIT_LENGTH - range.end) >> (Self::BIT_LENGTH - range.end);
+    /// Scatters the lower bits of `value` to the positions of the set bits of `mask`, keeping
+    /// their order, and leaves all bits not selected by `mask` untouched; this is also known as
+    /// parallel bit deposit (`PDEP`).
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0u32;
+    ///
+    /// value.deposit_by_mask(0b1010_1010, 0b1001);
+    /// assert_eq!(value, 0b1000_0010);
+    ///
+    /// value.deposit_by_mask(0b0000_1111, 0b0110);
+    /// assert_eq!(value, 0b1000_0110);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if there are `1`s in `value` beyond the lower N bits, where N is the
+    /// number of set bits in `mask`.
+    #[track_caller]
+    #[inline]
+    fn deposit_by_mask(&mut self, mask: Self, value: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let len = (0..Self::BIT_LENGTH)
+            .filter(|&bit| mask.get_bit(bit))
+            .count();
+        assert!(
+            (len..Self::BIT_LENGTH).all(|bit| !value.get_bit(bit)),
+            "value does not fit into mask"
+        );
 
-                    // shift away low bits
-                    bits >> range.start
-                }
+        let mut index = 0;
+        for bit in 0..Self::BIT_LENGTH {
+            if mask.get_bit(bit) {
+                self.set_bit(bit, value.get_bit(index));
+                index += 1;
             }
+        }
+        self
+    }
 
-            #[track_caller]
-            #[inline]
-            fn set_bit(&mut self, bit: usize, value: bool) -> &mut Self {
-                assert!(bit < Self::BIT_LENGTH);
+    /// Obtains the bits of all ranges in `ranges` concatenated into one value, with the bits of
+    /// the first range in the lowest bits of the result; this is useful for fields that are split
+    /// across several places of a bit field.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// // the base address of an x86 segment descriptor is split over two ranges
+    /// let descriptor: u64 = 0x1200_0034_5678_0000;
+    ///
+    /// assert_eq!(descriptor.get_split_bits(&[16..40, 56..64]), 0x1234_5678);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if any range is out of bounds of the bit field, if two ranges
+    /// overlap, or if the ranges together contain more than `BIT_LENGTH` bits.
+    #[track_caller]
+    #[inline]
+    fn get_split_bits(&self, ranges: &[Range<usize>]) -> Self
+    where
+        Self: Sized + Default,
+    {
+        check_split_ranges(ranges, Self::BIT_LENGTH, Self::BIT_LENGTH);
 
-                if value {
-                    *self |= 1 << bit;
-                } else {
-                    *self &= !(1 << bit);
-                }
+        let mut value = Self::default();
+        for (index, bit) in ranges.iter().cloned().flatten().enumerate() {
+            value.set_bit(index, self.get_bit(bit));
+        }
+        value
+    }
 
-                self
-            }
+    /// Sets the bits of all ranges in `ranges` to the lower bits of `value`, with the first range
+    /// receiving the lowest bits; this is the inverse of `get_split_bits`.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut descriptor = 0u64;
+    ///
+    /// descriptor.set_split_bits(&[16..40, 56..64], 0x1234_5678);
+    /// descriptor.set_split_bits(&[0..16, 48..52], 0xf_ffff);
+    /// assert_eq!(descriptor, 0x120f_0034_5678_ffff);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if any range is out of bounds of the bit field, if two ranges
+    /// overlap, or if there are `1`s in `value` beyond the lower N bits, where N is the total
+    /// length of the ranges.
+    #[track_caller]
+    #[inline]
+    fn set_split_bits(&mut self, ranges: &[Range<usize>], value: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let len = check_split_ranges(ranges, Self::BIT_LENGTH, Self::BIT_LENGTH);
+        assert!(
+            (len..Self::BIT_LENGTH).all(|bit| !value.get_bit(bit)),
+            "value does not fit into bit ranges"
+        );
 
-            #[track_caller]
-            #[inline]
-            fn set_bits<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self {
-                let range = to_regular_range(&range, Self::BIT_LENGTH);
+        for (index, bit) in ranges.iter().cloned().flatten().enumerate() {
+            self.set_bit(bit, value.get_bit(index));
+        }
+        self
+    }
 
-                assert!(range.start < Self::BIT_LENGTH);
-                assert!(range.end <= Self::BIT_LENGTH);
-                assert!(range.start <= range.end);
-                assert!(range.start == range.end && value == 0 ||
-                        value << (Self::BIT_LENGTH - (range.end - range.start)) >>
-                            (Self::BIT_LENGTH - (range.end - range.start)) == value,
-                        "value does not fit into bit range");
-                if range.start != range.end {
-                    let bitmask: Self = !(!0 << (Self::BIT_LENGTH - range.end) >>
-                                        (Self::BIT_LENGTH - range.end) >>
-                                        range.start << range.start);
+    /// Sets several fields at once, each given as a range and the value for its bits. All values
+    /// are checked before anything is written, and the result is stored into `self` with a single
+    /// write, so no intermediate state with only some of the fields updated is ever visible.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut register = 0xffff_0000u32;
+    ///
+    /// register.set_fields(&[(0..4, 0xa), (8..16, 0x5c), (28..32, 0)]);
+    /// assert_eq!(register, 0x0fff_5c0a);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if any range is out of bounds of the bit field, if two ranges
+    /// overlap, or if any value has `1`s beyond the length of its range. `self` is left unchanged
+    /// in all of these cases.
+    #[track_caller]
+    #[inline]
+    fn set_fields(&mut self, fields: &[(Range<usize>, Self)]) -> &mut Self
+    where
+        Self: Sized,
+    {
+        check_disjoint_ranges(fields.iter().map(|(range, _)| range), Self::BIT_LENGTH);
+        for (range, value) in fields {
+            assert!(
+                (range.len()..Self::BIT_LENGTH).all(|bit| !value.get_bit(bit)),
+                "value does not fit into bit range"
+            );
+        }
 
-                    // set bits
-                    *self = (*self & bitmask) | (value << range.start);
-                }
-                self
+        let mut bits = self.get_bits(..);
+        for (range, value) in fields {
+            for (index, bit) in range.clone().enumerate() {
+                bits.set_bit(bit, value.get_bit(index));
             }
         }
-    )*)
-}
-
-bitfield_numeric_impl! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
-
-impl<T: BitField> BitArray<T> for [T] {
-    #[inline]
-    fn bit_length(&self) -> usize {
-        self.len() * T::BIT_LENGTH
+        *self = bits;
+        self
     }
 
+    /// Returns a copy of `self` with several fields set at once, like `set_fields`.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let control = 0u16.with_fields(&[(0..3, 0b101), (12..16, 0x9)]);
+    /// assert_eq!(control, 0x9005);
+    /// ```
     #[track_caller]
     #[inline]
-    fn get_bit(&self, bit: usize) -> bool {
-        let slice_index = bit / T::BIT_LENGTH;
-        let bit_index = bit % T::BIT_LENGTH;
-        self[slice_index].get_bit(bit_index)
+    fn with_fields(mut self, fields: &[(Range<usize>, Self)]) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_fields(fields);
+        self
     }
 
-    #[track_caller]
+    /// Obtains the range of bits specified by `range` left at their position, with all other bits
+    /// set to `0`; this is useful for comparing a field with constants that are already shifted.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// const MODE_USER: u32 = 0b10 << 4;
+    /// let status: u32 = 0xf7a3_1025;
+    ///
+    /// assert_eq!(status.get_bits_masked(4..6), MODE_USER);
+    /// assert_eq!(status.get_bits_masked(8..16), 0x1000);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit field.
+    #[track_caller]
+    #[inline]
+    fn get_bits_masked<T: RangeBounds<usize>>(&self, range: T) -> Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+
+        let mut bits = self.get_bits(..);
+        for bit in (0..range.start).chain(range.end..Self::BIT_LENGTH) {
+            bits.set_bit(bit, false);
+        }
+        bits
+    }
+
+    /// Sets the range of bits defined by the range `range` to the bits of `value` at the same
+    /// positions, i.e. `value` is already shifted into place; all other bits are left untouched.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// const MODE_USER: u32 = 0b10 << 4;
+    /// let mut status: u32 = 0xf7a3_1005;
+    ///
+    /// status.set_bits_masked(4..6, MODE_USER);
+    /// assert_eq!(status, 0xf7a3_1025);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// in `value` outside of the range.
+    #[track_caller]
+    #[inline]
+    fn set_bits_masked<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+        assert!(
+            (0..Self::BIT_LENGTH).all(|bit| range.contains(&bit) || !value.get_bit(bit)),
+            "value does not fit into bit range"
+        );
+
+        for bit in range {
+            self.set_bit(bit, value.get_bit(bit));
+        }
+        self
+    }
+
+    /// Adds `n` to the range of bits specified by `range`, treating the bits as an unsigned
+    /// integer, and wraps around at the boundary of the range; returns whether an overflow
+    /// occurred. All bits outside of the range are left untouched.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u16 = 0xa_ffe;
+    ///
+    /// assert_eq!(value.overflowing_add_to_bits(0..12, 1), false);
+    /// assert_eq!(value, 0xa_fff);
+    /// assert_eq!(value.overflowing_add_to_bits(0..12, 2), true);
+    /// assert_eq!(value, 0xa_001);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `n`, where N is the length of the range.
+    #[track_caller]
+    #[inline]
+    fn overflowing_add_to_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> bool
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+        assert!(
+            (range.len()..Self::BIT_LENGTH).all(|bit| !n.get_bit(bit)),
+            "value does not fit into bit range"
+        );
+
+        let mut carry = false;
+        for (index, bit) in range.enumerate() {
+            let (a, b) = (self.get_bit(bit), n.get_bit(index));
+            self.set_bit(bit, a ^ b ^ carry);
+            carry = a && b || carry && (a ^ b);
+        }
+        carry
+    }
+
+    /// Subtracts `n` from the range of bits specified by `range`, treating the bits as an
+    /// unsigned integer, and wraps around at the boundary of the range; returns whether an
+    /// overflow occurred. All bits outside of the range are left untouched.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u8 = 0b11_001_111;
+    ///
+    /// assert_eq!(value.overflowing_sub_from_bits(3..6, 1), false);
+    /// assert_eq!(value, 0b11_000_111);
+    /// assert_eq!(value.overflowing_sub_from_bits(3..6, 1), true);
+    /// assert_eq!(value, 0b11_111_111);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `n`, where N is the length of the range.
+    #[track_caller]
+    #[inline]
+    fn overflowing_sub_from_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> bool
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+        assert!(
+            (range.len()..Self::BIT_LENGTH).all(|bit| !n.get_bit(bit)),
+            "value does not fit into bit range"
+        );
+
+        let mut borrow = false;
+        for (index, bit) in range.enumerate() {
+            let (a, b) = (self.get_bit(bit), n.get_bit(index));
+            self.set_bit(bit, a ^ b ^ borrow);
+            borrow = !a && b || borrow && !(a ^ b);
+        }
+        borrow
+    }
+
+    /// Adds `n` to the range of bits specified by `range`, treating the bits as an unsigned
+    /// integer, and wraps around at the boundary of the range.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// // a 12-bit sequence number in the upper bits
+    /// let mut header: u16 = 0xfff_3;
+    ///
+    /// header.wrapping_add_to_bits(4..16, 1);
+    /// assert_eq!(header, 0x000_3);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `n`, where N is the length of the range.
+    #[track_caller]
+    #[inline]
+    fn wrapping_add_to_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.overflowing_add_to_bits(range, n);
+        self
+    }
+
+    /// Subtracts `n` from the range of bits specified by `range`, treating the bits as an
+    /// unsigned integer, and wraps around at the boundary of the range.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut header: u16 = 0x000_3;
+    ///
+    /// header.wrapping_sub_from_bits(4..16, 1);
+    /// assert_eq!(header, 0xfff_3);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `n`, where N is the length of the range.
+    #[track_caller]
+    #[inline]
+    fn wrapping_sub_from_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.overflowing_sub_from_bits(range, n);
+        self
+    }
+
+    /// Adds `n` to the range of bits specified by `range`, treating the bits as an unsigned
+    /// integer, unless this would overflow the range, in which case `None` is returned and the
+    /// value is left unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u8 = 0b10_110_01;
+    ///
+    /// assert!(value.checked_add_to_bits(2..5, 1).is_some());
+    /// assert_eq!(value, 0b10_111_01);
+    /// assert!(value.checked_add_to_bits(2..5, 1).is_none());
+    /// assert_eq!(value, 0b10_111_01);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `n`, where N is the length of the range.
+    #[track_caller]
+    #[inline]
+    fn checked_add_to_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> Option<&mut Self>
+    where
+        Self: Sized,
+    {
+        let mut bits = self.get_bits(..);
+        if bits.overflowing_add_to_bits(range, n) {
+            None
+        } else {
+            *self = bits;
+            Some(self)
+        }
+    }
+
+    /// Subtracts `n` from the range of bits specified by `range`, treating the bits as an
+    /// unsigned integer, unless this would overflow the range, in which case `None` is returned
+    /// and the value is left unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u8 = 0b10_001_01;
+    ///
+    /// assert!(value.checked_sub_from_bits(2..5, 1).is_some());
+    /// assert_eq!(value, 0b10_000_01);
+    /// assert!(value.checked_sub_from_bits(2..5, 1).is_none());
+    /// assert_eq!(value, 0b10_000_01);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `n`, where N is the length of the range.
+    #[track_caller]
+    #[inline]
+    fn checked_sub_from_bits<T: RangeBounds<usize>>(
+        &mut self,
+        range: T,
+        n: Self,
+    ) -> Option<&mut Self>
+    where
+        Self: Sized,
+    {
+        let mut bits = self.get_bits(..);
+        if bits.overflowing_sub_from_bits(range, n) {
+            None
+        } else {
+            *self = bits;
+            Some(self)
+        }
+    }
+
+    /// Adds `n` to the range of bits specified by `range`, treating the bits as an unsigned
+    /// integer, and sets all bits of the range to `1` if this would overflow the range.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// // a 3-bit retry count
+    /// let mut status: u8 = 0b100_00001;
+    ///
+    /// status.saturating_add_to_bits(5..8, 0b10);
+    /// assert_eq!(status, 0b110_00001);
+    /// status.saturating_add_to_bits(5..8, 0b10);
+    /// assert_eq!(status, 0b111_00001);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `n`, where N is the length of the range.
+    #[track_caller]
+    #[inline]
+    fn saturating_add_to_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        if self.checked_add_to_bits(range.clone(), n).is_none() {
+            for bit in range {
+                self.set_bit(bit, true);
+            }
+        }
+        self
+    }
+
+    /// Subtracts `n` from the range of bits specified by `range`, treating the bits as an
+    /// unsigned integer, and sets all bits of the range to `0` if this would overflow the range.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u8 = 0b1_010_1111;
+    ///
+    /// value.saturating_sub_from_bits(4..7, 0b011);
+    /// assert_eq!(value, 0b1_000_1111);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `n`, where N is the length of the range.
+    #[track_caller]
+    #[inline]
+    fn saturating_sub_from_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        if self.checked_sub_from_bits(range.clone(), n).is_none() {
+            for bit in range {
+                self.set_bit(bit, false);
+            }
+        }
+        self
+    }
+
+    /// Adds `n` to the range of bits specified by `range`, treating the bits as an unsigned
+    /// integer.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u32 = 0x1234_5678;
+    ///
+    /// value.add_to_bits(8..16, 0x10);
+    /// assert_eq!(value, 0x1234_6678);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the addition overflows the range, if the range is out of bounds
+    /// of the bit field, or if there are `1`s not in the lower N bits of `n`, where N is the
+    /// length of the range.
+    #[track_caller]
+    #[inline]
+    fn add_to_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.checked_add_to_bits(range, n)
+            .expect("attempt to add to bit range with overflow")
+    }
+
+    /// Subtracts `n` from the range of bits specified by `range`, treating the bits as an
+    /// unsigned integer.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u32 = 0x1234_5678;
+    ///
+    /// value.sub_from_bits(8..16, 0x10);
+    /// assert_eq!(value, 0x1234_4678);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the subtraction overflows the range, if the range is out of
+    /// bounds of the bit field, or if there are `1`s not in the lower N bits of `n`, where N is
+    /// the length of the range.
+    #[track_caller]
+    #[inline]
+    fn sub_from_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.checked_sub_from_bits(range, n)
+            .expect("attempt to subtract from bit range with overflow")
+    }
+
+    /// Reverses the order of the bits in the range `range`, leaving all other bits untouched.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u16 = 0xf_0b_f;
+    ///
+    /// value.reverse_bits_in(4..12);
+    /// assert_eq!(value, 0xf_d0_f);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field.
+    #[track_caller]
+    #[inline]
+    fn reverse_bits_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+
+        for (low, high) in range
+            .clone()
+            .zip(range.rev())
+            .take_while(|(low, high)| low < high)
+        {
+            self.swap_bits(low, high);
+        }
+        self
+    }
+
+    /// Rotates the bits in the range `range` by `n` places towards the end of the range; the bits
+    /// shifted out at the end of the range wrap around to its start. All bits outside of the range
+    /// are left untouched.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u16 = 0xa_c1_b;
+    ///
+    /// value.rotate_left_in(4..12, 2);
+    /// assert_eq!(value, 0xa_07_b);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field.
+    #[track_caller]
+    #[inline]
+    fn rotate_left_in<T: RangeBounds<usize>>(&mut self, range: T, n: usize) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+
+        let len = range.len();
+        // reduce the rotation first, so that adding it to an index can't overflow
+        let n = n % len.max(1);
+        let bits = self.get_bits(..);
+        for (index, bit) in range.clone().enumerate() {
+            self.set_bit(range.start + (index + n) % len, bits.get_bit(bit));
+        }
+        self
+    }
+
+    /// Rotates the bits in the range `range` by `n` places towards the start of the range; the
+    /// bits shifted out at the start of the range wrap around to its end. All bits outside of the
+    /// range are left untouched.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u16 = 0xa_07_b;
+    ///
+    /// value.rotate_right_in(4..12, 2);
+    /// assert_eq!(value, 0xa_c1_b);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field.
+    #[track_caller]
+    #[inline]
+    fn rotate_right_in<T: RangeBounds<usize>>(&mut self, range: T, n: usize) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        let len = range.len().max(1);
+        self.rotate_left_in(range, len - n % len)
+    }
+
+    /// Swaps the bits at the indexes `a` and `b`.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u8 = 0b0000_0001;
+    ///
+    /// value.swap_bits(0, 7);
+    /// assert_eq!(value, 0b1000_0000);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if either bit index is out of bounds of the bit field.
+    #[track_caller]
+    #[inline]
+    fn swap_bits(&mut self, a: usize, b: usize) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let (bit_a, bit_b) = (self.get_bit(a), self.get_bit(b));
+        self.set_bit(a, bit_b).set_bit(b, bit_a)
+    }
+
+    /// Reverses the order of the bytes in the range `range`, leaving all other bits untouched;
+    /// this is useful for fields stored with a different byte order than the rest of a value.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value: u64 = 0x1122_3344_5566_7788;
+    ///
+    /// value.swap_bytes_in(16..48);
+    /// assert_eq!(value, 0x1122_6655_4433_7788);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if its start or
+    /// length is not a multiple of 8.
+    #[track_caller]
+    #[inline]
+    fn swap_bytes_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+        assert!(
+            range.start % 8 == 0 && range.len() % 8 == 0,
+            "bit range is not byte aligned"
+        );
+
+        let bytes = range.len() / 8;
+        for byte in 0..bytes / 2 {
+            for bit in 0..8 {
+                self.swap_bits(
+                    range.start + byte * 8 + bit,
+                    range.start + (bytes - 1 - byte) * 8 + bit,
+                );
+            }
+        }
+        self
+    }
+
+    /// Returns the index of the most significant set bit in the range `range`, or `None` if all
+    /// bits in the range are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let pending: u32 = 0x0104_0010;
+    ///
+    /// assert_eq!(pending.highest_set_bit_in(8..24), Some(18));
+    /// assert_eq!(pending.highest_set_bit_in(..), Some(24));
+    /// assert_eq!(pending.highest_set_bit_in(5..18), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit field.
+    #[track_caller]
+    #[inline]
+    fn highest_set_bit_in<T: RangeBounds<usize>>(&self, range: T) -> Option<usize> {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+
+        range.rev().find(|&bit| self.get_bit(bit))
+    }
+
+    /// Returns the index of the least significant set bit in the range `range`, or `None` if all
+    /// bits in the range are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let pending: u32 = 0x0104_0010;
+    ///
+    /// assert_eq!(pending.lowest_set_bit_in(8..24), Some(18));
+    /// assert_eq!(pending.lowest_set_bit_in(..), Some(4));
+    /// assert_eq!(pending.lowest_set_bit_in(19..24), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit field.
+    #[track_caller]
+    #[inline]
+    fn lowest_set_bit_in<T: RangeBounds<usize>>(&self, range: T) -> Option<usize> {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+
+        range.clone().find(|&bit| self.get_bit(bit))
+    }
+
+    /// Returns the number of `0` bits at the most significant end of the range `range`; this is
+    /// the length of the range if all of its bits are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value: u16 = 0x0f30;
+    ///
+    /// assert_eq!(value.leading_zeros_in(4..16), 4);
+    /// assert_eq!(value.leading_zeros_in(0..4), 4);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit field.
+    #[track_caller]
+    #[inline]
+    fn leading_zeros_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        match self.highest_set_bit_in(range.clone()) {
+            Some(bit) => range.end - 1 - bit,
+            None => range.len(),
+        }
+    }
+
+    /// Returns the number of `0` bits at the least significant end of the range `range`; this is
+    /// the length of the range if all of its bits are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value: u16 = 0x0f30;
+    ///
+    /// assert_eq!(value.trailing_zeros_in(2..16), 2);
+    /// assert_eq!(value.trailing_zeros_in(12..16), 4);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit field.
+    #[track_caller]
+    #[inline]
+    fn trailing_zeros_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        match self.lowest_set_bit_in(range.clone()) {
+            Some(bit) => bit - range.start,
+            None => range.len(),
+        }
+    }
+
+    /// Returns the value whose bit `i` is bit `table[i]` of `self`, for all `i` in `0..N`; all bits
+    /// from `N` on are `0`. Bits of `self` may be used several times or not at all.
+    ///
+    /// For applying the same permutation to many values, `BitPermutation` precomputes a faster
+    /// representation and also supports different input and output types.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value: u8 = 0b1100_0101;
+    ///
+    /// assert_eq!(value.permute_bits(&[4, 5, 6, 7, 0, 1, 2, 3]), 0b0101_1100);
+    /// assert_eq!(value.permute_bits(&[0, 0, 7]), 0b111);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `N` is larger than the bit length of the bit field, or if any
+    /// index in `table` is out of bounds of the bit field.
+    #[track_caller]
+    #[inline]
+    fn permute_bits<const N: usize>(&self, table: &[u8; N]) -> Self
+    where
+        Self: Sized + Default,
+    {
+        assert!(
+            N <= Self::BIT_LENGTH,
+            "permutation table is longer than the bit field"
+        );
+
+        let mut bits = Self::default();
+        for (bit, &source) in table.iter().enumerate() {
+            bits.set_bit(bit, self.get_bit(source as usize));
+        }
+        bits
+    }
+}
+
+pub trait BitArray<T: BitField> {
+    /// Returns the length, eg number of bits, in this bit array.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert_eq!([0u8, 4u8, 8u8].bit_length(), 24);
+    /// assert_eq!([0u32, 5u32].bit_length(), 64);
+    /// ```
+    fn bit_length(&self) -> usize;
+
+    /// Obtains the bit at the index `bit`; note that index 0 is the least significant bit, while
+    /// index `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value: [u32; 1] = [0b110101];
+    ///
+    /// assert_eq!(value.get_bit(1), false);
+    /// assert_eq!(value.get_bit(2), true);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit array.
+    fn get_bit(&self, bit: usize) -> bool;
+
+    /// Obtains the range of bits specified by `range`; note that index 0 is the least significant
+    /// bit, while index `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value: [u32; 2] = [0b110101, 0b11];
+    ///
+    /// assert_eq!(value.get_bits(0..3), 0b101);
+    /// assert_eq!(value.get_bits(..6), 0b110101);
+    /// assert_eq!(value.get_bits(31..33), 0b10);
+    /// assert_eq!(value.get_bits(5..=32), 0b1_0000_0000_0000_0000_0000_0000_001);
+    /// assert_eq!(value.get_bits(34..), 0);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit array, or if the range can't be contained by the bit field T.
+    fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> T;
+
+    /// Sets the bit at the index `bit` to the value `value` (where true means a value of '1' and
+    /// false means a value of '0'); note that index 0 is the least significant bit, while index
+    /// `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0u32];
+    ///
+    /// value.set_bit(1, true);
+    /// assert_eq!(value, [2u32]);
+    ///
+    /// value.set_bit(3, true);
+    /// assert_eq!(value, [10u32]);
+    ///
+    /// value.set_bit(1, false);
+    /// assert_eq!(value, [8u32]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of the bounds of the bit array.
+    fn set_bit(&mut self, bit: usize, value: bool);
+
+    /// Sets the range of bits defined by the range `range` to the lower bits of `value`; to be
+    /// specific, if the range is N bits long, the N lower bits of `value` will be used; if any of
+    /// the other bits in `value` are set to 1, this function will panic.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0u32, 0u32];
+    ///
+    /// value.set_bits(0..2, 0b11);
+    /// assert_eq!(value, [0b11, 0u32]);
+    ///
+    /// value.set_bits(31..35, 0b1010);
+    /// assert_eq!(value, [0x0003, 0b101]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array,
+    /// if the range can't be contained by the bit field T, or if there are `1`s
+    /// not in the lower N bits of `value`.
+    fn set_bits<U: RangeBounds<usize>>(&mut self, range: U, value: T);
+
+    /// Copies the range of bits specified by `range` into `out`, starting at bit 0 of `out[0]`;
+    /// unlike `get_bits`, the range may be longer than `T::BIT_LENGTH` and may start at any bit.
+    ///
+    /// Only the first `range.len()` bits of `out`, rounded up to whole elements, are written; the
+    /// bits of the last written element that lie past the end of the range are set to `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value: [u8; 4] = [0x21, 0x43, 0x65, 0x87];
+    /// let mut out = [0u8; 3];
+    ///
+    /// value.get_bits_into(4..24, &mut out);
+    /// assert_eq!(out, [0x32, 0x54, 0x06]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit array, or if `out` is too short to hold the range.
+    #[track_caller]
+    #[inline]
+    fn get_bits_into<U: RangeBounds<usize>>(&self, range: U, out: &mut [T]) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+        assert!(range.len() <= out.bit_length());
+
+        let len = range.len();
+        for (index, bit) in range.enumerate() {
+            out.set_bit(index, self.get_bit(bit));
+        }
+        for bit in len..len.div_ceil(T::BIT_LENGTH) * T::BIT_LENGTH {
+            out.set_bit(bit, false);
+        }
+    }
+
+    /// Sets the range of bits defined by the range `range` to the lower bits of `src`; unlike
+    /// `set_bits`, the range may be longer than `T::BIT_LENGTH` and may start at any bit.
+    ///
+    /// Only the first `range.len()` bits of `src`, rounded up to whole elements, are read; if any
+    /// of the bits of the last read element past the length of the range are set to `1`, this
+    /// function will panic.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0u8; 4];
+    ///
+    /// value.set_bits_from(4..24, &[0x32, 0x54, 0x06]);
+    /// assert_eq!(value, [0x20, 0x43, 0x65, 0x00]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array, if `src` is too
+    /// short to hold the range, or if there are `1`s past the length of the range in the last
+    /// element read from `src`.
+    #[track_caller]
+    #[inline]
+    fn set_bits_from<U: RangeBounds<usize>>(&mut self, range: U, src: &[T]) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+        assert!(range.len() <= src.bit_length());
+
+        let len = range.len();
+        assert!(
+            (len..len.div_ceil(T::BIT_LENGTH) * T::BIT_LENGTH).all(|bit| !src.get_bit(bit)),
+            "value does not fit into bit range"
+        );
+
+        for (index, bit) in range.enumerate() {
+            self.set_bit(bit, src.get_bit(index));
+        }
+    }
+
+    /// Copies the bits in the range `src` to the bits starting at index `dest`, like
+    /// `slice::copy_within` does for elements; the two ranges may overlap.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0x0fu8, 0x00, 0x00];
+    ///
+    /// value.copy_within_bits(0..8, 6);
+    /// assert_eq!(value, [0xcf, 0x03, 0x00]);
+    ///
+    /// value.copy_within_bits(4..14, 2);
+    /// assert_eq!(value, [0xf3, 0x00, 0x00]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the source range is out of bounds of the bit array, or if the
+    /// destination range `dest..dest + src.len()` is.
+    #[track_caller]
+    #[inline]
+    fn copy_within_bits<U: RangeBounds<usize>>(&mut self, src: U, dest: usize) {
+        let src = to_regular_range(&src, self.bit_length());
+
+        assert!(src.start <= src.end);
+        assert!(src.end <= self.bit_length());
+        assert!(dest <= self.bit_length() - src.len());
+
+        if dest <= src.start {
+            for bit in src.clone() {
+                let value = self.get_bit(bit);
+                self.set_bit(dest + (bit - src.start), value);
+            }
+        } else {
+            // copy from the back, so that no source bits are overwritten before they are read
+            for bit in src.clone().rev() {
+                let value = self.get_bit(bit);
+                self.set_bit(dest + (bit - src.start), value);
+            }
+        }
+    }
+
+    /// Shifts all bits of the bit array by `n` places towards the most significant bit, as if the
+    /// bit array was a single integer; the `n` least significant bits are set to `0` and the `n`
+    /// most significant bits are shifted out.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0xf0u8, 0x0f];
+    ///
+    /// value.shl_bits(6);
+    /// assert_eq!(value, [0x00, 0xfc]);
+    ///
+    /// value.shl_bits(16);
+    /// assert_eq!(value, [0x00, 0x00]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    fn shl_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        let n = n.min(len);
+        self.copy_within_bits(..len - n, n);
+        for bit in 0..n {
+            self.set_bit(bit, false);
+        }
+    }
+
+    /// Shifts all bits of the bit array by `n` places towards the least significant bit, as if
+    /// the bit array was a single integer; the `n` most significant bits are set to `0` and the
+    /// `n` least significant bits are shifted out.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0xf0u8, 0x0f];
+    ///
+    /// value.shr_bits(6);
+    /// assert_eq!(value, [0x3f, 0x00]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    fn shr_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        let n = n.min(len);
+        self.copy_within_bits(n.., 0);
+        for bit in len - n..len {
+            self.set_bit(bit, false);
+        }
+    }
+
+    /// Rotates all bits of the bit array by `n` places towards the most significant bit, as if
+    /// the bit array was a single integer; the `n` most significant bits wrap around to the
+    /// least significant end.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0xf0u8, 0x0f];
+    ///
+    /// value.rotate_left_bits(6);
+    /// assert_eq!(value, [0x03, 0xfc]);
+    ///
+    /// value.rotate_left_bits(16);
+    /// assert_eq!(value, [0x03, 0xfc]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    fn rotate_left_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        if len != 0 {
+            // rotate by reversing both parts and then the whole array
+            let mid = len - n % len;
+            reverse_array_bits(self, 0..mid);
+            reverse_array_bits(self, mid..len);
+            reverse_array_bits(self, 0..len);
+        }
+    }
+
+    /// Rotates all bits of the bit array by `n` places towards the least significant bit, as if
+    /// the bit array was a single integer; the `n` least significant bits wrap around to the
+    /// most significant end.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0xf0u8, 0x0f];
+    ///
+    /// value.rotate_right_bits(6);
+    /// assert_eq!(value, [0x3f, 0xc0]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    fn rotate_right_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        if len != 0 {
+            self.rotate_left_bits(len - n % len);
+        }
+    }
+
+    /// Shifts the bits in the range `range` by `n` places, leaving all other bits untouched; a
+    /// positive `n` shifts towards the most significant bit and a negative `n` towards the least
+    /// significant bit. Bits shifted past either end of the range are discarded and the vacated
+    /// bits are set to `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0xffu8, 0x0f];
+    ///
+    /// value.shift_range(4..12, 2);
+    /// assert_eq!(value, [0xcf, 0x0f]);
+    ///
+    /// value.shift_range(4..12, -3);
+    /// assert_eq!(value, [0xff, 0x01]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn shift_range<U: RangeBounds<usize>>(&mut self, range: U, n: isize) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        let shift = n.unsigned_abs().min(range.len());
+        if n >= 0 {
+            self.copy_within_bits(range.start..range.end - shift, range.start + shift);
+            for bit in range.start..range.start + shift {
+                self.set_bit(bit, false);
+            }
+        } else {
+            self.copy_within_bits(range.start + shift..range.end, range.start);
+            for bit in range.end - shift..range.end {
+                self.set_bit(bit, false);
+            }
+        }
+    }
+
+    /// Obtains the range of bits specified by `range` as a value of the integral type `V`, which
+    /// may be wider or narrower than the element type `T`; note that index 0 is the least
+    /// significant bit, while index `length() - 1` is the most significant bit.
+    ///
+    /// The bits are moved one element at a time as plain bit patterns, so signed types are never
+    /// sign-extended.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0x21u8, 0x43, 0x65, 0x87, 0xa9];
+    ///
+    /// assert_eq!(value.get_bits_as::<u32>(4..36), 0x98765432);
+    /// assert_eq!(value.get_bits_as::<u16>(12..24), 0x654);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit array, or if the range can't be contained by the bit field `V`.
+    #[track_caller]
+    #[inline]
+    fn get_bits_as<V>(&self, range: impl RangeBounds<usize>) -> V
+    where
+        T: PermutationWord,
+        V: PermutationWord,
+    {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+        assert!(range.len() <= V::BIT_LENGTH);
+
+        let mut bits = 0;
+        for (index, bit) in range.enumerate() {
+            bits |= u128::from(self.get_bit(bit)) << index;
+        }
+        V::from_bits(bits)
+    }
+
+    /// Sets the range of bits defined by the range `range` to the lower bits of `value`, a value
+    /// of the integral type `V`, which may be wider or narrower than the element type `T`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0u8; 5];
+    ///
+    /// value.set_bits_as(4..36, 0x98765432u32);
+    /// assert_eq!(value, [0x20, 0x43, 0x65, 0x87, 0x09]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array, if the range can't
+    /// be contained by the bit field `V`, or if there are `1`s not in the lower N bits of `value`.
+    #[track_caller]
+    #[inline]
+    fn set_bits_as<V>(&mut self, range: impl RangeBounds<usize>, value: V)
+    where
+        T: PermutationWord,
+        V: PermutationWord,
+    {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+        assert!(range.len() <= V::BIT_LENGTH);
+
+        let bits = value.to_bits();
+        assert!(
+            range.len() == u128::BIT_LENGTH || bits >> range.len() == 0,
+            "value does not fit into bit range"
+        );
+
+        for (index, bit) in range.enumerate() {
+            self.set_bit(bit, (bits >> index) & 1 != 0);
+        }
+    }
+
+    /// Obtains the bit at the index `bit` like `get_bit`, but without checking that the index is
+    /// in bounds in release builds.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value: [u32; 2] = [0b110101, 0b1];
+    ///
+    /// assert_eq!(unsafe { value.get_bit_unchecked(32) }, true);
+    /// ```
+    ///
+    /// ## Safety
+    ///
+    /// `bit` must be less than `self.bit_length()`, i.e. the call must not panic when made with
+    /// `get_bit` instead. This is only checked with `debug_assert!`.
+    #[track_caller]
+    #[inline]
+    unsafe fn get_bit_unchecked(&self, bit: usize) -> bool {
+        self.get_bit(bit)
+    }
+
+    /// Obtains the range of bits specified by `range` like `get_bits`, but without checking the
+    /// range in release builds.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value: [u32; 2] = [0b110101, 0b11];
+    ///
+    /// assert_eq!(unsafe { value.get_bits_unchecked(31..33) }, 0b10);
+    /// ```
+    ///
+    /// ## Safety
+    ///
+    /// The range must be in bounds of the bit array and not longer than `T::BIT_LENGTH`, i.e. the
+    /// call must not panic when made with `get_bits` instead. This is only checked with
+    /// `debug_assert!`.
+    #[track_caller]
+    #[inline]
+    unsafe fn get_bits_unchecked<U: RangeBounds<usize>>(&self, range: U) -> T {
+        self.get_bits(range)
+    }
+
+    /// Sets the bit at the index `bit` like `set_bit`, but without checking that the index is in
+    /// bounds in release builds.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0u32, 0u32];
+    ///
+    /// unsafe { value.set_bit_unchecked(33, true) };
+    /// assert_eq!(value, [0u32, 2u32]);
+    /// ```
+    ///
+    /// ## Safety
+    ///
+    /// `bit` must be less than `self.bit_length()`, i.e. the call must not panic when made with
+    /// `set_bit` instead. This is only checked with `debug_assert!`.
+    #[track_caller]
+    #[inline]
+    unsafe fn set_bit_unchecked(&mut self, bit: usize, value: bool) {
+        self.set_bit(bit, value);
+    }
+
+    /// Sets the range of bits defined by the range `range` to the lower bits of `value` like
+    /// `set_bits`, but without checking the range and value in release builds.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0u32, 0u32];
+    ///
+    /// unsafe { value.set_bits_unchecked(31..35, 0b1010) };
+    /// assert_eq!(value, [0x0000_0000, 0b101]);
+    /// ```
+    ///
+    /// ## Safety
+    ///
+    /// The range must be in bounds of the bit array and not longer than `T::BIT_LENGTH`, and
+    /// `value` must fit into it, i.e. the call must not panic when made with `set_bits` instead.
+    /// This is only checked with `debug_assert!`.
+    #[track_caller]
+    #[inline]
+    unsafe fn set_bits_unchecked<U: RangeBounds<usize>>(&mut self, range: U, value: T) {
+        self.set_bits(range, value);
+    }
+
+    /// Obtains the bits of all ranges in `ranges` concatenated into one value, with the bits of
+    /// the first range in the lowest bits of the result; the ranges may lie in different elements
+    /// of the bit array.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0x34u8, 0x12, 0xff];
+    ///
+    /// assert_eq!(value.get_split_bits(&[4..8, 16..18, 8..10]), 0b10_11_0011);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if any range is out of bounds of the bit array, if two ranges
+    /// overlap, or if the ranges together contain more bits than a single element.
+    #[track_caller]
+    #[inline]
+    fn get_split_bits(&self, ranges: &[Range<usize>]) -> T
+    where
+        T: Default,
+    {
+        check_split_ranges(ranges, self.bit_length(), T::BIT_LENGTH);
+
+        let mut value = T::default();
+        for (index, bit) in ranges.iter().cloned().flatten().enumerate() {
+            value.set_bit(index, self.get_bit(bit));
+        }
+        value
+    }
+
+    /// Sets the bits of all ranges in `ranges` to the lower bits of `value`, with the first range
+    /// receiving the lowest bits; this is the inverse of `get_split_bits`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0u8; 3];
+    ///
+    /// value.set_split_bits(&[4..8, 16..18, 8..10], 0b10_11_0011);
+    /// assert_eq!(value, [0x30, 0x02, 0x03]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if any range is out of bounds of the bit array, if two ranges
+    /// overlap, or if there are `1`s in `value` beyond the lower N bits, where N is the total
+    /// length of the ranges.
+    #[track_caller]
+    #[inline]
+    fn set_split_bits(&mut self, ranges: &[Range<usize>], value: T) {
+        let len = check_split_ranges(ranges, self.bit_length(), T::BIT_LENGTH);
+        assert!(
+            (len..T::BIT_LENGTH).all(|bit| !value.get_bit(bit)),
+            "value does not fit into bit ranges"
+        );
+
+        for (index, bit) in ranges.iter().cloned().flatten().enumerate() {
+            self.set_bit(bit, value.get_bit(index));
+        }
+    }
+
+    /// Obtains the range of bits specified by `range` left at their position within their
+    /// element, with all other bits set to `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0x1234u16, 0xabcd];
+    ///
+    /// assert_eq!(value.get_bits_masked(20..28), 0x0bc0);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array, or if the range
+    /// covers bits of more than one element.
+    #[track_caller]
+    #[inline]
+    fn get_bits_masked<U: RangeBounds<usize>>(&self, range: U) -> T {
+        let range = to_regular_range(&range, self.bit_length());
+        let (slice_index, bit_range) = element_bit_range::<T>(range, self.bit_length());
+
+        let offset = slice_index * T::BIT_LENGTH;
+        self.get_bits(offset..offset + T::BIT_LENGTH)
+            .get_bits_masked(bit_range)
+    }
+
+    /// Sets the range of bits defined by the range `range` to the bits of `value` at the same
+    /// positions within their element, i.e. `value` is already shifted into place.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0x1234u16, 0xabcd];
+    ///
+    /// value.set_bits_masked(20..28, 0x0560);
+    /// assert_eq!(value, [0x1234, 0xa56d]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array, if the range
+    /// covers bits of more than one element, or if there are `1`s in `value` outside of the
+    /// range.
+    #[track_caller]
+    #[inline]
+    fn set_bits_masked<U: RangeBounds<usize>>(&mut self, range: U, value: T) {
+        let range = to_regular_range(&range, self.bit_length());
+        let (slice_index, bit_range) = element_bit_range::<T>(range, self.bit_length());
+
+        let offset = slice_index * T::BIT_LENGTH;
+        let mut word = self.get_bits(offset..offset + T::BIT_LENGTH);
+        word.set_bits_masked(bit_range, value);
+        self.set_bits(offset..offset + T::BIT_LENGTH, word);
+    }
+
+    /// Sets all bits in the range `range` to `0`; the range may span any number of elements.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0xffu8; 4];
+    ///
+    /// value.clear_range(4..28);
+    /// assert_eq!(value, [0x0f, 0x00, 0x00, 0xf0]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn clear_range<U: RangeBounds<usize>>(&mut self, range: U)
+    where
+        T: Clone,
+    {
+        self.set_range_to(range, false);
+    }
+
+    /// Sets all bits in the range `range` to `1`; the range may span any number of elements.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// // mark the 4 KiB frames of the second gigabyte as used
+    /// let mut frames = [0u64; 8192];
+    ///
+    /// frames.fill_range(1 << 18..2 << 18);
+    /// assert!(frames[..4096].iter().all(|&word| word == 0));
+    /// assert!(frames[4096..].iter().all(|&word| word == u64::MAX));
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn fill_range<U: RangeBounds<usize>>(&mut self, range: U)
+    where
+        T: Clone,
+    {
+        self.set_range_to(range, true);
+    }
+
+    /// Sets all bits in the range `range` to `value`; the range may span any number of elements.
+    ///
+    /// Partial elements at the start and end of the range are updated with masks, while all
+    /// elements in between are overwritten as a whole.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let mut value = [0u16; 3];
+    ///
+    /// value.set_range_to(12..36, true);
+    /// assert_eq!(value, [0xf000, 0xffff, 0x000f]);
+    ///
+    /// value.set_range_to(14..34, false);
+    /// assert_eq!(value, [0x3000, 0x0000, 0x000c]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn set_range_to<U: RangeBounds<usize>>(&mut self, range: U, value: bool)
+    where
+        T: Clone,
+    {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        for bit in range {
+            self.set_bit(bit, value);
+        }
+    }
+
+    /// Returns the index of the most significant set bit in the range `range`, or `None` if all
+    /// bits in the range are `0`; the range may span any number of elements.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0x01u8, 0x00, 0x80, 0x00];
+    ///
+    /// assert_eq!(value.highest_set_bit_in(..), Some(23));
+    /// assert_eq!(value.highest_set_bit_in(..23), Some(0));
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn highest_set_bit_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        range.rev().find(|&bit| self.get_bit(bit))
+    }
+
+    /// Returns the index of the least significant set bit in the range `range`, or `None` if all
+    /// bits in the range are `0`; the range may span any number of elements.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0x01u8, 0x00, 0x80, 0x00];
+    ///
+    /// assert_eq!(value.lowest_set_bit_in(..), Some(0));
+    /// assert_eq!(value.lowest_set_bit_in(1..), Some(23));
+    /// assert_eq!(value.lowest_set_bit_in(1..23), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn lowest_set_bit_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+        let mut range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        range.find(|&bit| self.get_bit(bit))
+    }
+
+    /// Returns the number of `0` bits at the most significant end of the range `range`; this is
+    /// the length of the range if all of its bits are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0x01u8, 0x00, 0x80, 0x00];
+    ///
+    /// assert_eq!(value.leading_zeros_in(..), 8);
+    /// assert_eq!(value.leading_zeros_in(..23), 22);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn leading_zeros_in<U: RangeBounds<usize>>(&self, range: U) -> usize {
+        let range = to_regular_range(&range, self.bit_length());
+        match self.highest_set_bit_in(range.clone()) {
+            Some(bit) => range.end - 1 - bit,
+            None => range.len(),
+        }
+    }
+
+    /// Returns the number of `0` bits at the least significant end of the range `range`; this is
+    /// the length of the range if all of its bits are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0x01u8, 0x00, 0x80, 0x00];
+    ///
+    /// assert_eq!(value.trailing_zeros_in(1..), 22);
+    /// assert_eq!(value.trailing_zeros_in(24..), 8);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn trailing_zeros_in<U: RangeBounds<usize>>(&self, range: U) -> usize {
+        let range = to_regular_range(&range, self.bit_length());
+        match self.lowest_set_bit_in(range.clone()) {
+            Some(bit) => bit - range.start,
+            None => range.len(),
+        }
+    }
+}
+
+/// An internal macro used for implementing BitField on the standard integral types.
+macro_rules! bitfield_numeric_impl {
+    ($($t:ty)*) => ($(
+        impl BitField for $t {
+            const BIT_LENGTH: usize = ::core::mem::size_of::<Self>() as usize * 8;
+
+            #[track_caller]
+            #[inline]
+            fn get_bit(&self, bit: usize) -> bool {
+                assert!(bit < Self::BIT_LENGTH);
+
+                unsafe { self.get_bit_unchecked(bit) }
+            }
+
+            #[track_caller]
+            #[inline]
+            fn get_bits<T: RangeBounds<usize>>(&self, range: T) -> Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                unsafe { self.get_bits_unchecked(range) }
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bit(&mut self, bit: usize, value: bool) -> &mut Self {
+                assert!(bit < Self::BIT_LENGTH);
+
+                unsafe { self.set_bit_unchecked(bit, value) }
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bits<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+                assert!(range.start == range.end && value == 0 ||
+                        value << (Self::BIT_LENGTH - (range.end - range.start)) >>
+                            (Self::BIT_LENGTH - (range.end - range.start)) == value,
+                        "value does not fit into bit range");
+
+                unsafe { self.set_bits_unchecked(range, value) }
+            }
+
+            #[track_caller]
+            #[inline]
+            unsafe fn get_bit_unchecked(&self, bit: usize) -> bool {
+                debug_assert!(bit < Self::BIT_LENGTH);
+                hint::assert_unchecked(bit < Self::BIT_LENGTH);
+
+                (*self & (1 << bit)) != 0
+            }
+
+            #[track_caller]
+            #[inline]
+            unsafe fn get_bits_unchecked<T: RangeBounds<usize>>(&self, range: T) -> Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                debug_assert!(range.start < Self::BIT_LENGTH);
+                debug_assert!(range.end <= Self::BIT_LENGTH);
+                debug_assert!(range.start <= range.end);
+                hint::assert_unchecked(range.start <= range.end && range.end <= Self::BIT_LENGTH);
+
+                if range.start == range.end {
+                    0
+                } else {
+                    // shift away high bits
+                    let bits = *self << (Self::BIT_LENGTH - range.end) >> (Self::BIT_LENGTH - range.end);
+
+                    // shift away low bits
+                    bits >> range.start
+                }
+            }
+
+            #[track_caller]
+            #[inline]
+            unsafe fn set_bit_unchecked(&mut self, bit: usize, value: bool) -> &mut Self {
+                debug_assert!(bit < Self::BIT_LENGTH);
+                hint::assert_unchecked(bit < Self::BIT_LENGTH);
+
+                if value {
+                    *self |= 1 << bit;
+                } else {
+                    *self &= !(1 << bit);
+                }
+
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            unsafe fn set_bits_unchecked<T: RangeBounds<usize>>(
+                &mut self,
+                range: T,
+                value: Self,
+            ) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                debug_assert!(range.start < Self::BIT_LENGTH);
+                debug_assert!(range.end <= Self::BIT_LENGTH);
+                debug_assert!(range.start <= range.end);
+                debug_assert!(range.start == range.end && value == 0 ||
+                              value << (Self::BIT_LENGTH - (range.end - range.start)) >>
+                                  (Self::BIT_LENGTH - (range.end - range.start)) == value,
+                              "value does not fit into bit range");
+                hint::assert_unchecked(range.start <= range.end && range.end <= Self::BIT_LENGTH);
+
+                if range.start != range.end {
+                    let bitmask: Self = !(!0 << (Self::BIT_LENGTH - range.end) >>
+                                        (Self::BIT_LENGTH - range.end) >>
+                                        range.start << range.start);
+
+                    // set bits
+                    *self = (*self & bitmask) | (value << range.start);
+                }
+                self
+            }
+
+            #[inline]
+            fn extract_by_mask(&self, mask: Self) -> Self {
+                MaskOps::extract(*self, mask)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn deposit_by_mask(&mut self, mask: Self, value: Self) -> &mut Self {
+                let len = mask.count_ones() as usize;
+                assert!(len == Self::BIT_LENGTH || value >> len == 0,
+                        "value does not fit into mask");
+
+                *self = (*self & !mask) | MaskOps::deposit(value, mask);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_fields(&mut self, fields: &[(Range<usize>, Self)]) -> &mut Self {
+                check_disjoint_ranges(fields.iter().map(|(range, _)| range), Self::BIT_LENGTH);
+
+                let mut mask: Self = 0;
+                let mut bits: Self = 0;
+                for (range, value) in fields {
+                    let len = range.end - range.start;
+                    assert!(len == Self::BIT_LENGTH || *value >> len == 0,
+                            "value does not fit into bit range");
+
+                    if len != 0 {
+                        mask |= Self::range_mask(range.clone());
+                        bits |= *value << range.start;
+                    }
+                }
+
+                *self = (*self & !mask) | bits;
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn get_bits_masked<T: RangeBounds<usize>>(&self, range: T) -> Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                *self & Self::range_mask(range)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bits_masked<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                let mask = Self::range_mask(range);
+                assert!(value & !mask == 0, "value does not fit into bit range");
+
+                *self = (*self & !mask) | value;
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn overflowing_add_to_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> bool {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                let len = range.end - range.start;
+                assert!(len == Self::BIT_LENGTH || n >> len == 0, "value does not fit into bit range");
+                if len == 0 {
+                    return false;
+                }
+
+                // align the range with the most significant bit, so that the carry out of the
+                // range is the carry out of the whole value
+                let shift = Self::BIT_LENGTH - range.end;
+                let mask = Self::range_mask(range.clone());
+                let a = (*self & mask) << shift;
+                let b = n << (Self::BIT_LENGTH - len);
+                let sum = a.wrapping_add(b);
+
+                *self = (*self & !mask) | ((sum >> shift) & mask);
+                ((a & b) | ((a | b) & !sum)) >> (Self::BIT_LENGTH - 1) & 1 != 0
+            }
+
+            #[track_caller]
+            #[inline]
+            fn overflowing_sub_from_bits<T: RangeBounds<usize>>(&mut self, range: T, n: Self) -> bool {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                let len = range.end - range.start;
+                assert!(len == Self::BIT_LENGTH || n >> len == 0, "value does not fit into bit range");
+                if len == 0 {
+                    return false;
+                }
+
+                // align the range with the most significant bit, so that the borrow out of the
+                // range is the borrow out of the whole value
+                let shift = Self::BIT_LENGTH - range.end;
+                let mask = Self::range_mask(range.clone());
+                let a = (*self & mask) << shift;
+                let b = n << (Self::BIT_LENGTH - len);
+                let difference = a.wrapping_sub(b);
+
+                *self = (*self & !mask) | ((difference >> shift) & mask);
+                ((!a & b) | (!(a ^ b) & difference)) >> (Self::BIT_LENGTH - 1) & 1 != 0
+            }
+
+            #[track_caller]
+            #[inline]
+            fn reverse_bits_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                if range.start == range.end {
+                    return self;
+                }
+
+                // align the range with the most significant bit, so that reversing the whole
+                // value moves the reversed range to the least significant bits
+                let mask = Self::range_mask(range.clone());
+                let reversed = ((*self & mask) << (Self::BIT_LENGTH - range.end)).reverse_bits();
+
+                *self = (*self & !mask) | (reversed << range.start);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn rotate_left_in<T: RangeBounds<usize>>(&mut self, range: T, n: usize) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                let len = range.end - range.start;
+                if len == Self::BIT_LENGTH {
+                    *self = self.rotate_left((n % len) as u32);
+                    return self;
+                }
+                let n = n % len.max(1);
+                if n == 0 {
+                    return self;
+                }
+
+                // the most significant bit of the field is always cleared, so the right shift
+                // is a logical shift even for signed types
+                let field_mask = Self::range_mask(0..len);
+                let field = (*self >> range.start) & field_mask;
+                let rotated = ((field << n) | (field >> (len - n))) & field_mask;
+
+                *self = (*self & !Self::range_mask(range.clone())) | (rotated << range.start);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn swap_bytes_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+                assert!(range.start % 8 == 0 && (range.end - range.start) % 8 == 0,
+                        "bit range is not byte aligned");
+
+                if range.start == range.end {
+                    return self;
+                }
+
+                // align the range with the most significant byte, so that swapping the bytes of
+                // the whole value moves the swapped range to the least significant bytes
+                let mask = Self::range_mask(range.clone());
+                let swapped = ((*self & mask) << (Self::BIT_LENGTH - range.end)).swap_bytes();
+
+                *self = (*self & !mask) | (swapped << range.start);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn highest_set_bit_in<T: RangeBounds<usize>>(&self, range: T) -> Option<usize> {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                match *self & Self::range_mask(range) {
+                    0 => None,
+                    bits => Some(Self::BIT_LENGTH - 1 - bits.leading_zeros() as usize),
+                }
+            }
+
+            #[track_caller]
+            #[inline]
+            fn lowest_set_bit_in<T: RangeBounds<usize>>(&self, range: T) -> Option<usize> {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start <= range.end);
+
+                match *self & Self::range_mask(range) {
+                    0 => None,
+                    bits => Some(bits.trailing_zeros() as usize),
+                }
+            }
+        }
+    )*)
+}
+
+bitfield_numeric_impl! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
+
+/// An internal macro used for implementing BitField on the standard wrapper types by forwarding to
+/// the wrapped bit field.
+macro_rules! bitfield_wrapper_impl {
+    ($($w:ident)*) => ($(
+        impl<T: BitField> BitField for $w<T> {
+            const BIT_LENGTH: usize = T::BIT_LENGTH;
+
+            #[track_caller]
+            #[inline]
+            fn get_bit(&self, bit: usize) -> bool {
+                self.0.get_bit(bit)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> Self {
+                $w(self.0.get_bits(range))
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bit(&mut self, bit: usize, value: bool) -> &mut Self {
+                self.0.set_bit(bit, value);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bits<U: RangeBounds<usize>>(&mut self, range: U, value: Self) -> &mut Self {
+                self.0.set_bits(range, value.0);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            unsafe fn get_bit_unchecked(&self, bit: usize) -> bool {
+                self.0.get_bit_unchecked(bit)
+            }
+
+            #[track_caller]
+            #[inline]
+            unsafe fn get_bits_unchecked<U: RangeBounds<usize>>(&self, range: U) -> Self {
+                $w(self.0.get_bits_unchecked(range))
+            }
+
+            #[track_caller]
+            #[inline]
+            unsafe fn set_bit_unchecked(&mut self, bit: usize, value: bool) -> &mut Self {
+                self.0.set_bit_unchecked(bit, value);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            unsafe fn set_bits_unchecked<U: RangeBounds<usize>>(
+                &mut self,
+                range: U,
+                value: Self,
+            ) -> &mut Self {
+                self.0.set_bits_unchecked(range, value.0);
+                self
+            }
+
+            #[inline]
+            fn extract_by_mask(&self, mask: Self) -> Self {
+                $w(self.0.extract_by_mask(mask.0))
+            }
+
+            #[track_caller]
+            #[inline]
+            fn deposit_by_mask(&mut self, mask: Self, value: Self) -> &mut Self {
+                self.0.deposit_by_mask(mask.0, value.0);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn overflowing_add_to_bits<U: RangeBounds<usize>>(&mut self, range: U, n: Self) -> bool {
+                self.0.overflowing_add_to_bits(range, n.0)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn overflowing_sub_from_bits<U: RangeBounds<usize>>(&mut self, range: U, n: Self) -> bool {
+                self.0.overflowing_sub_from_bits(range, n.0)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn reverse_bits_in<U: RangeBounds<usize>>(&mut self, range: U) -> &mut Self {
+                self.0.reverse_bits_in(range);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn rotate_left_in<U: RangeBounds<usize>>(&mut self, range: U, n: usize) -> &mut Self {
+                self.0.rotate_left_in(range, n);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn swap_bytes_in<U: RangeBounds<usize>>(&mut self, range: U) -> &mut Self {
+                self.0.swap_bytes_in(range);
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn highest_set_bit_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+                self.0.highest_set_bit_in(range)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn lowest_set_bit_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+                self.0.lowest_set_bit_in(range)
+            }
+        }
+    )*)
+}
+
+bitfield_wrapper_impl! { Wrapping Saturating }
+
+impl<T: BitField> BitArray<T> for [T] {
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.len() * T::BIT_LENGTH
+    }
+
+    #[track_caller]
+    #[inline]
+    fn get_bit(&self, bit: usize) -> bool {
+        let slice_index = bit / T::BIT_LENGTH;
+        let bit_index = bit % T::BIT_LENGTH;
+        self[slice_index].get_bit(bit_index)
+    }
+
+    #[track_caller]
     #[inline]
     fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> T {
         let range = to_regular_range(&range, self.bit_length());
@@ -361,6 +2633,635 @@ impl<T: BitField> BitArray<T> for [T] {
             );
         }
     }
+
+    #[track_caller]
+    #[inline]
+    fn get_bits_into<U: RangeBounds<usize>>(&self, range: U, out: &mut [T]) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+        assert!(range.len() <= out.bit_length());
+
+        let len = range.len();
+        copy_bit_chunks(self, range.start, out, 0, len);
+        for bit in len..len.div_ceil(T::BIT_LENGTH) * T::BIT_LENGTH {
+            out.set_bit(bit, false);
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn set_bits_from<U: RangeBounds<usize>>(&mut self, range: U, src: &[T]) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+        assert!(range.len() <= src.bit_length());
+
+        let len = range.len();
+        assert!(
+            (len..len.div_ceil(T::BIT_LENGTH) * T::BIT_LENGTH).all(|bit| !src.get_bit(bit)),
+            "value does not fit into bit range"
+        );
+
+        copy_bit_chunks(src, 0, self, range.start, len);
+    }
+
+    #[track_caller]
+    #[inline]
+    fn copy_within_bits<U: RangeBounds<usize>>(&mut self, src: U, dest: usize) {
+        let src = to_regular_range(&src, self.bit_length());
+
+        assert!(src.start <= src.end);
+        assert!(src.end <= self.bit_length());
+        assert!(dest <= self.bit_length() - src.len());
+
+        let len = src.len();
+        if dest <= src.start {
+            let mut offset = 0;
+            while offset < len {
+                let (src_bit, dest_bit) = (src.start + offset, dest + offset);
+                let chunk = chunk_len::<T>(src_bit, dest_bit, len - offset);
+                let word = self[src_bit / T::BIT_LENGTH].get_bits(..);
+                move_bits(
+                    word,
+                    src_bit % T::BIT_LENGTH,
+                    &mut self[dest_bit / T::BIT_LENGTH],
+                    dest_bit % T::BIT_LENGTH,
+                    chunk,
+                );
+                offset += chunk;
+            }
+        } else {
+            // copy from the back, so that no source bits are overwritten before they are read
+            let mut remaining = len;
+            while remaining > 0 {
+                let (src_end, dest_end) = (src.start + remaining, dest + remaining);
+                let chunk = ((src_end - 1) % T::BIT_LENGTH + 1)
+                    .min((dest_end - 1) % T::BIT_LENGTH + 1)
+                    .min(remaining);
+                remaining -= chunk;
+                let (src_bit, dest_bit) = (src.start + remaining, dest + remaining);
+                let word = self[src_bit / T::BIT_LENGTH].get_bits(..);
+                move_bits(
+                    word,
+                    src_bit % T::BIT_LENGTH,
+                    &mut self[dest_bit / T::BIT_LENGTH],
+                    dest_bit % T::BIT_LENGTH,
+                    chunk,
+                );
+            }
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn shl_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        shift_bits_left(self, 0..len, n);
+    }
+
+    #[track_caller]
+    #[inline]
+    fn shr_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        shift_bits_right(self, 0..len, n);
+    }
+
+    #[track_caller]
+    #[inline]
+    fn rotate_left_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        if len != 0 {
+            rotate_bits(self, 0, len - n % len, len);
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn rotate_right_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        if len != 0 {
+            rotate_bits(self, 0, n % len, len);
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn shift_range<U: RangeBounds<usize>>(&mut self, range: U, n: isize) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        if n >= 0 {
+            shift_bits_left(self, range, n.unsigned_abs());
+        } else {
+            shift_bits_right(self, range, n.unsigned_abs());
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn get_bits_as<V>(&self, range: impl RangeBounds<usize>) -> V
+    where
+        T: PermutationWord,
+        V: PermutationWord,
+    {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+        assert!(range.len() <= V::BIT_LENGTH);
+
+        let mut bits = 0;
+        for (slice_index, element_bits) in element_ranges::<T>(range.clone()) {
+            let offset = slice_index * T::BIT_LENGTH + element_bits.start - range.start;
+            bits |= bits_in(self[slice_index], element_bits) << offset;
+        }
+        V::from_bits(bits)
+    }
+
+    #[track_caller]
+    #[inline]
+    fn set_bits_as<V>(&mut self, range: impl RangeBounds<usize>, value: V)
+    where
+        T: PermutationWord,
+        V: PermutationWord,
+    {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+        assert!(range.len() <= V::BIT_LENGTH);
+
+        let bits = value.to_bits();
+        assert!(
+            range.len() == u128::BIT_LENGTH || bits >> range.len() == 0,
+            "value does not fit into bit range"
+        );
+
+        for (slice_index, element_bits) in element_ranges::<T>(range.clone()) {
+            let offset = slice_index * T::BIT_LENGTH + element_bits.start - range.start;
+            let word = T::from_bits(bits >> offset << element_bits.start);
+            self[slice_index].set_bits_masked(element_bits, word);
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    unsafe fn get_bit_unchecked(&self, bit: usize) -> bool {
+        debug_assert!(bit < self.bit_length());
+
+        let slice_index = bit / T::BIT_LENGTH;
+        let bit_index = bit % T::BIT_LENGTH;
+        self.get_unchecked(slice_index).get_bit_unchecked(bit_index)
+    }
+
+    #[track_caller]
+    #[inline]
+    unsafe fn get_bits_unchecked<U: RangeBounds<usize>>(&self, range: U) -> T {
+        let range = to_regular_range(&range, self.bit_length());
+
+        debug_assert!(range.start <= range.end);
+        debug_assert!(range.end <= self.bit_length());
+        debug_assert!(range.len() <= T::BIT_LENGTH);
+
+        let slice_start = range.start / T::BIT_LENGTH;
+        let slice_end = range.end / T::BIT_LENGTH;
+        let bit_start = range.start % T::BIT_LENGTH;
+        let bit_end = range.end % T::BIT_LENGTH;
+        let len = range.len();
+
+        if slice_start == slice_end {
+            self.get_unchecked(slice_start)
+                .get_bits_unchecked(bit_start..bit_end)
+        } else if bit_end == 0 {
+            self.get_unchecked(slice_start)
+                .get_bits_unchecked(bit_start..T::BIT_LENGTH)
+        } else {
+            let mut ret = self
+                .get_unchecked(slice_start)
+                .get_bits_unchecked(bit_start..T::BIT_LENGTH);
+            ret.set_bits_unchecked(
+                (T::BIT_LENGTH - bit_start)..len,
+                self.get_unchecked(slice_end).get_bits_unchecked(0..bit_end),
+            );
+            ret
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    unsafe fn set_bit_unchecked(&mut self, bit: usize, value: bool) {
+        debug_assert!(bit < self.bit_length());
+
+        let slice_index = bit / T::BIT_LENGTH;
+        let bit_index = bit % T::BIT_LENGTH;
+        self.get_unchecked_mut(slice_index)
+            .set_bit_unchecked(bit_index, value);
+    }
+
+    #[track_caller]
+    #[inline]
+    unsafe fn set_bits_unchecked<U: RangeBounds<usize>>(&mut self, range: U, value: T) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        debug_assert!(range.start <= range.end);
+        debug_assert!(range.end <= self.bit_length());
+        debug_assert!(range.len() <= T::BIT_LENGTH);
+
+        let slice_start = range.start / T::BIT_LENGTH;
+        let slice_end = range.end / T::BIT_LENGTH;
+        let bit_start = range.start % T::BIT_LENGTH;
+        let bit_end = range.end % T::BIT_LENGTH;
+
+        if slice_start == slice_end {
+            self.get_unchecked_mut(slice_start)
+                .set_bits_unchecked(bit_start..bit_end, value);
+        } else if bit_end == 0 {
+            self.get_unchecked_mut(slice_start)
+                .set_bits_unchecked(bit_start..T::BIT_LENGTH, value);
+        } else {
+            self.get_unchecked_mut(slice_start).set_bits_unchecked(
+                bit_start..T::BIT_LENGTH,
+                value.get_bits_unchecked(0..T::BIT_LENGTH - bit_start),
+            );
+            self.get_unchecked_mut(slice_end).set_bits_unchecked(
+                0..bit_end,
+                value.get_bits_unchecked(T::BIT_LENGTH - bit_start..T::BIT_LENGTH),
+            );
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn get_bits_masked<U: RangeBounds<usize>>(&self, range: U) -> T {
+        let range = to_regular_range(&range, self.bit_length());
+        let (slice_index, bit_range) = element_bit_range::<T>(range, self.bit_length());
+
+        self[slice_index].get_bits_masked(bit_range)
+    }
+
+    #[track_caller]
+    #[inline]
+    fn set_bits_masked<U: RangeBounds<usize>>(&mut self, range: U, value: T) {
+        let range = to_regular_range(&range, self.bit_length());
+        let (slice_index, bit_range) = element_bit_range::<T>(range, self.bit_length());
+
+        self[slice_index].set_bits_masked(bit_range, value);
+    }
+
+    #[track_caller]
+    #[inline]
+    fn set_range_to<U: RangeBounds<usize>>(&mut self, range: U, value: bool)
+    where
+        T: Clone,
+    {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        if range.is_empty() {
+            return;
+        }
+
+        let word = filled_like(&self[range.start / T::BIT_LENGTH], value);
+
+        let slice_start = range.start / T::BIT_LENGTH;
+        let slice_end = range.end / T::BIT_LENGTH;
+        let bit_start = range.start % T::BIT_LENGTH;
+        let bit_end = range.end % T::BIT_LENGTH;
+
+        if slice_start == slice_end {
+            self[slice_start]
+                .set_bits_masked(bit_start..bit_end, word.get_bits_masked(bit_start..bit_end));
+            return;
+        }
+
+        let mut whole = slice_start..slice_end;
+        if bit_start != 0 {
+            self[slice_start].set_bits_masked(bit_start.., word.get_bits_masked(bit_start..));
+            whole.start += 1;
+        }
+        if bit_end != 0 {
+            self[slice_end].set_bits_masked(..bit_end, word.get_bits_masked(..bit_end));
+        }
+        self[whole].fill(word);
+    }
+
+    #[track_caller]
+    #[inline]
+    fn highest_set_bit_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        element_ranges::<T>(range)
+            .rev()
+            .find_map(|(slice_index, bit_range)| {
+                self[slice_index]
+                    .highest_set_bit_in(bit_range)
+                    .map(|bit| slice_index * T::BIT_LENGTH + bit)
+            })
+    }
+
+    #[track_caller]
+    #[inline]
+    fn lowest_set_bit_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        element_ranges::<T>(range).find_map(|(slice_index, bit_range)| {
+            self[slice_index]
+                .lowest_set_bit_in(bit_range)
+                .map(|bit| slice_index * T::BIT_LENGTH + bit)
+        })
+    }
+}
+
+/// Copies the bits in the range `src_range` of `src` to the bits of `dst` starting at index
+/// `dst_start`; the source and destination may start at any bit offset.
+///
+/// ```rust
+/// use bit_field::copy_bits;
+///
+/// let src = [0xabu8, 0xcd];
+/// let mut dst = [0u8; 3];
+///
+/// copy_bits(&src, 4..16, &mut dst, 10);
+/// assert_eq!(dst, [0x00, 0x68, 0x33]);
+/// ```
+///
+/// ## Panics
+///
+/// This function will panic if `src_range` is out of bounds of `src`, or if the destination range
+/// `dst_start..dst_start + src_range.len()` is out of bounds of `dst`.
+#[track_caller]
+#[inline]
+pub fn copy_bits<T: BitField, U: RangeBounds<usize>>(
+    src: &[T],
+    src_range: U,
+    dst: &mut [T],
+    dst_start: usize,
+) {
+    let src_range = to_regular_range(&src_range, src.bit_length());
+
+    assert!(src_range.start <= src_range.end);
+    assert!(src_range.end <= src.bit_length());
+    assert!(dst_start <= dst.bit_length());
+    assert!(src_range.len() <= dst.bit_length() - dst_start);
+
+    copy_bit_chunks(src, src_range.start, dst, dst_start, src_range.len());
+}
+
+/// Shifts the bits in `range` by `n` places towards the end of the range, filling the vacated
+/// bits with `0`.
+#[track_caller]
+#[inline]
+fn shift_bits_left<T: BitField>(slice: &mut [T], range: Range<usize>, n: usize) {
+    if n < range.len() {
+        slice.copy_within_bits(range.start..range.end - n, range.start + n);
+        clear_bits(slice, range.start..range.start + n);
+    } else {
+        clear_bits(slice, range);
+    }
+}
+
+/// Shifts the bits in `range` by `n` places towards the start of the range, filling the vacated
+/// bits with `0`.
+#[track_caller]
+#[inline]
+fn shift_bits_right<T: BitField>(slice: &mut [T], range: Range<usize>, n: usize) {
+    if n < range.len() {
+        slice.copy_within_bits(range.start + n..range.end, range.start);
+        clear_bits(slice, range.end - n..range.end);
+    } else {
+        clear_bits(slice, range);
+    }
+}
+
+/// Sets all bits in `range` to `0`.
+#[track_caller]
+#[inline]
+fn clear_bits<T: BitField>(slice: &mut [T], range: Range<usize>) {
+    let mut zero = None;
+    for (slice_index, bits) in element_ranges::<T>(range) {
+        let zero = zero.get_or_insert_with(|| filled_like(&slice[slice_index], false));
+        slice[slice_index].set_bits_masked(bits, zero.get_bits(..));
+    }
+}
+
+/// Returns the bits of `word` in `range` in the lower bits of a `u128`, with all other bits `0`.
+#[inline]
+fn bits_in<T: PermutationWord>(word: T, range: Range<usize>) -> u128 {
+    match range.len() {
+        0 => 0,
+        len => (word.to_bits() >> range.start) & (u128::MAX >> (u128::BIT_LENGTH - len)),
+    }
+}
+
+/// Returns a bit field of the same type as `field` with all bits set to `value`.
+#[inline]
+fn filled_like<T: BitField>(field: &T, value: bool) -> T {
+    let mut word = field.get_bits(..);
+    for bit in 0..T::BIT_LENGTH {
+        word.set_bit(bit, value);
+    }
+    word
+}
+
+/// Reverses the order of the bits in `range` of `array`.
+#[track_caller]
+#[inline]
+fn reverse_array_bits<T: BitField, A: BitArray<T> + ?Sized>(array: &mut A, range: Range<usize>) {
+    let (mut low, mut high) = (range.start, range.end);
+    while low + 1 < high {
+        high -= 1;
+        let bit = array.get_bit(low);
+        let other = array.get_bit(high);
+        array.set_bit(low, other);
+        array.set_bit(high, bit);
+        low += 1;
+    }
+}
+
+/// Rotates the bits in `start..end` so that the bits in `mid..end` move to the start of the
+/// range, using the Gries-Mills block swap algorithm.
+#[track_caller]
+#[inline]
+fn rotate_bits<T: BitField>(slice: &mut [T], start: usize, mid: usize, end: usize) {
+    let (mut left, mut right) = (mid - start, end - mid);
+    if left == 0 || right == 0 {
+        return;
+    }
+    while left != right {
+        if left > right {
+            swap_bits_ranges(slice, mid - left, mid, right);
+            left -= right;
+        } else {
+            swap_bits_ranges(slice, mid - left, mid + right - left, left);
+            right -= left;
+        }
+    }
+    swap_bits_ranges(slice, mid - left, mid, left);
+}
+
+/// Swaps the `len` bits starting at `a` with the `len` bits starting at `b`; the two ranges must
+/// not overlap.
+#[track_caller]
+#[inline]
+fn swap_bits_ranges<T: BitField>(slice: &mut [T], a: usize, b: usize, len: usize) {
+    let mut offset = 0;
+    while offset < len {
+        let (bit_a, bit_b) = (a + offset, b + offset);
+        let chunk = chunk_len::<T>(bit_a, bit_b, len - offset);
+        let (index_a, index_b) = (bit_a / T::BIT_LENGTH, bit_b / T::BIT_LENGTH);
+        // both elements may be the same, so read both before writing either
+        let word_a = slice[index_a].get_bits(..);
+        let word_b = slice[index_b].get_bits(..);
+        move_bits(
+            word_b,
+            bit_b % T::BIT_LENGTH,
+            &mut slice[index_a],
+            bit_a % T::BIT_LENGTH,
+            chunk,
+        );
+        move_bits(
+            word_a,
+            bit_a % T::BIT_LENGTH,
+            &mut slice[index_b],
+            bit_b % T::BIT_LENGTH,
+            chunk,
+        );
+        offset += chunk;
+    }
+}
+
+/// Moves the `len` bits of `src` starting at `src_start` to the bits of `dst` starting at
+/// `dst_start`, leaving all other bits of `dst` untouched; both ranges must lie within the bit
+/// field.
+///
+/// Unlike a `get_bits` and `set_bits` pair, this moves the plain bit pattern: for signed types,
+/// `get_bits` sign-extends the bits and `set_bits` fills the bits above the range with the sign.
+#[track_caller]
+#[inline]
+fn move_bits<T: BitField>(mut src: T, src_start: usize, dst: &mut T, dst_start: usize, len: usize) {
+    let range = dst_start..dst_start + len;
+    src.rotate_left_in(.., dst_start + T::BIT_LENGTH - src_start);
+    dst.set_bits_masked(range.clone(), src.get_bits_masked(range));
+}
+
+/// Returns the length of the next chunk of a copy from bit `src` to bit `dst` with `remaining`
+/// bits left, such that the chunk lies within a single element at both positions.
+#[inline]
+fn chunk_len<T: BitField>(src: usize, dst: usize, remaining: usize) -> usize {
+    (T::BIT_LENGTH - src % T::BIT_LENGTH)
+        .min(T::BIT_LENGTH - dst % T::BIT_LENGTH)
+        .min(remaining)
+}
+
+/// Copies the `len` bits of `src` starting at `src_start` to the bits of `dst` starting at
+/// `dst_start`; both ranges must be in bounds.
+#[track_caller]
+#[inline]
+fn copy_bit_chunks<T: BitField>(
+    src: &[T],
+    src_start: usize,
+    dst: &mut [T],
+    dst_start: usize,
+    len: usize,
+) {
+    let mut offset = 0;
+    while offset < len {
+        let (src_bit, dst_bit) = (src_start + offset, dst_start + offset);
+        let chunk = chunk_len::<T>(src_bit, dst_bit, len - offset);
+        move_bits(
+            src[src_bit / T::BIT_LENGTH].get_bits(..),
+            src_bit % T::BIT_LENGTH,
+            &mut dst[dst_bit / T::BIT_LENGTH],
+            dst_bit % T::BIT_LENGTH,
+            chunk,
+        );
+        offset += chunk;
+    }
+}
+
+/// Checks that all `ranges` lie within `bit_length` bits, that no two of them overlap, and that
+/// they contain at most `value_length` bits together; returns the total number of bits.
+#[track_caller]
+#[inline]
+fn check_split_ranges(ranges: &[Range<usize>], bit_length: usize, value_length: usize) -> usize {
+    let len = check_disjoint_ranges(ranges.iter(), bit_length);
+    assert!(
+        len <= value_length,
+        "bit ranges exceed the bit length of the value"
+    );
+    len
+}
+
+/// Checks that all `ranges` lie within `bit_length` bits and that no two of them overlap; returns
+/// the total number of bits.
+#[track_caller]
+#[inline]
+fn check_disjoint_ranges<'a, I>(ranges: I, bit_length: usize) -> usize
+where
+    I: Iterator<Item = &'a Range<usize>> + Clone,
+{
+    for (i, range) in ranges.clone().enumerate() {
+        assert!(range.start <= range.end);
+        assert!(range.end <= bit_length);
+        assert!(
+            ranges
+                .clone()
+                .take(i)
+                .all(|other| range.start.max(other.start) >= range.end.min(other.end)),
+            "bit ranges overlap"
+        );
+    }
+
+    ranges.map(|range| range.len()).sum()
+}
+
+/// Returns the index of the element that contains all bits in `range` and the range of these
+/// bits within that element.
+#[track_caller]
+#[inline]
+fn element_bit_range<T: BitField>(range: Range<usize>, bit_length: usize) -> (usize, Range<usize>) {
+    assert!(range.start < bit_length);
+    assert!(range.end <= bit_length);
+    assert!(range.start <= range.end);
+
+    let slice_index = range.start / T::BIT_LENGTH;
+    let offset = slice_index * T::BIT_LENGTH;
+    assert!(
+        range.end - offset <= T::BIT_LENGTH,
+        "bit range spans multiple elements"
+    );
+
+    (slice_index, range.start - offset..range.end - offset)
+}
+
+/// Splits `range` into the ranges of bits it covers within each element, together with the index
+/// of that element.
+#[inline]
+fn element_ranges<T: BitField>(
+    range: Range<usize>,
+) -> impl DoubleEndedIterator<Item = (usize, Range<usize>)> {
+    let slice_start = range.start / T::BIT_LENGTH;
+    let slice_end = range.end.div_ceil(T::BIT_LENGTH);
+
+    (slice_start..slice_end).map(move |slice_index| {
+        let offset = slice_index * T::BIT_LENGTH;
+        let start = range.start.max(offset) - offset;
+        let end = range.end.min(offset + T::BIT_LENGTH) - offset;
+        (slice_index, start..end)
+    })
 }
 
 #[inline]
@@ -0,0 +1,108 @@
+//! Internal helpers for working with bit masks on the standard integral types.
+
+use core::ops::Range;
+
+/// Extracts and deposits bits by an arbitrary mask, like the `PEXT` and `PDEP` instructions.
+pub trait MaskOps: Sized {
+    /// Gathers the bits of `self` selected by `mask` into the low bits of the result.
+    fn extract(self, mask: Self) -> Self;
+
+    /// Scatters the low bits of `self` to the positions of the set bits in `mask`.
+    fn deposit(self, mask: Self) -> Self;
+}
+
+/// An internal macro used for implementing MaskOps in software.
+macro_rules! portable_mask_ops_impl {
+    ($($t:ty)*) => ($(
+        impl MaskOps for $t {
+            #[inline]
+            fn extract(self, mut mask: Self) -> Self {
+                let mut result = 0;
+                let mut bit: Self = 1;
+                while mask != 0 {
+                    let lowest = mask & mask.wrapping_neg();
+                    if self & lowest != 0 {
+                        result |= bit;
+                    }
+                    bit <<= 1;
+                    mask &= mask.wrapping_sub(1);
+                }
+                result
+            }
+
+            #[inline]
+            fn deposit(self, mut mask: Self) -> Self {
+                let mut result = 0;
+                let mut bit: Self = 1;
+                while mask != 0 {
+                    let lowest = mask & mask.wrapping_neg();
+                    if self & bit != 0 {
+                        result |= lowest;
+                    }
+                    bit <<= 1;
+                    mask &= mask.wrapping_sub(1);
+                }
+                result
+            }
+        }
+    )*)
+}
+
+portable_mask_ops_impl! { u8 u16 u128 usize i8 i16 i32 i64 i128 isize }
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+portable_mask_ops_impl! { u32 u64 }
+
+/// An internal macro used for implementing MaskOps with the BMI2 instructions.
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+macro_rules! bmi2_mask_ops_impl {
+    ($($t:ty => $pext:ident, $pdep:ident;)*) => ($(
+        impl MaskOps for $t {
+            #[inline]
+            fn extract(self, mask: Self) -> Self {
+                // the `bmi2` target feature is statically enabled
+                unsafe { ::core::arch::x86_64::$pext(self, mask) }
+            }
+
+            #[inline]
+            fn deposit(self, mask: Self) -> Self {
+                // the `bmi2` target feature is statically enabled
+                unsafe { ::core::arch::x86_64::$pdep(self, mask) }
+            }
+        }
+    )*)
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+bmi2_mask_ops_impl! {
+    u32 => _pext_u32, _pdep_u32;
+    u64 => _pext_u64, _pdep_u64;
+}
+
+/// Creates the mask of a range of bits on the standard integral types.
+pub trait RangeMask {
+    /// Returns a value with all bits in `range` set to `1` and all other bits set to `0`.
+    fn range_mask(range: Range<usize>) -> Self;
+}
+
+/// An internal macro used for implementing RangeMask on the standard integral types.
+macro_rules! range_mask_impl {
+    ($($t:ty)*) => ($(
+        impl RangeMask for $t {
+            #[inline]
+            fn range_mask(range: Range<usize>) -> Self {
+                const BIT_LENGTH: usize = ::core::mem::size_of::<$t>() * 8;
+
+                if range.start == range.end {
+                    0
+                } else if range.end == BIT_LENGTH {
+                    !0 << range.start
+                } else {
+                    (!0 << range.start) & !(!0 << range.end)
+                }
+            }
+        }
+    )*)
+}
+
+range_mask_impl! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
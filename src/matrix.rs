@@ -0,0 +1,288 @@
+use core::ops::{BitXor, Mul, Range};
+use core::slice;
+
+use {BitArray, BitField};
+
+/// A matrix of bits with `ROWS` rows and `T::BIT_LENGTH` columns, stored row-major with one bit
+/// field per row.
+///
+/// Bit `j` of row `i` is the entry in row `i` and column `j`, so `BitMatrix<u8, 8>` is an 8x8
+/// matrix and `BitMatrix<u64, 64>` a 64x64 matrix. Arithmetic is over GF(2), i.e. addition is XOR
+/// and multiplication is AND.
+///
+/// ```rust
+/// use bit_field::BitMatrix;
+///
+/// let mut matrix = BitMatrix([0u8; 8]);
+///
+/// matrix.set(1, 6, true);
+/// assert!(matrix.get(1, 6));
+/// assert_eq!(matrix.0[1], 0b0100_0000);
+///
+/// let transposed = matrix.transpose();
+/// assert!(transposed.get(6, 1));
+/// assert_eq!(transposed.0[6], 0b0000_0010);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitMatrix<T, const ROWS: usize>(pub [T; ROWS]);
+
+impl<T: Copy + Default, const ROWS: usize> Default for BitMatrix<T, ROWS> {
+    #[inline]
+    fn default() -> Self {
+        BitMatrix([T::default(); ROWS])
+    }
+}
+
+impl<T, const ROWS: usize> From<[T; ROWS]> for BitMatrix<T, ROWS> {
+    #[inline]
+    fn from(rows: [T; ROWS]) -> Self {
+        BitMatrix(rows)
+    }
+}
+
+impl<T: BitField, const ROWS: usize> BitMatrix<T, ROWS> {
+    /// Returns the matrix with `1`s on the main diagonal and `0`s everywhere else.
+    ///
+    /// ```rust
+    /// use bit_field::BitMatrix;
+    ///
+    /// assert_eq!(BitMatrix::<u8, 4>::identity(), BitMatrix([0b0001, 0b0010, 0b0100, 0b1000]));
+    /// ```
+    #[inline]
+    pub fn identity() -> Self
+    where
+        T: Copy + Default,
+    {
+        let mut matrix = Self::default();
+        for i in 0..ROWS.min(T::BIT_LENGTH) {
+            matrix.set(i, i, true);
+        }
+        matrix
+    }
+
+    /// Returns the entry in row `row` and column `column`.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `row` or `column` is out of bounds of the matrix.
+    #[track_caller]
+    #[inline]
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        assert!(row < ROWS);
+        assert!(column < T::BIT_LENGTH);
+
+        self.0.get_bit(row * T::BIT_LENGTH + column)
+    }
+
+    /// Sets the entry in row `row` and column `column` to `value`.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `row` or `column` is out of bounds of the matrix.
+    #[track_caller]
+    #[inline]
+    pub fn set(&mut self, row: usize, column: usize, value: bool) -> &mut Self {
+        assert!(row < ROWS);
+        assert!(column < T::BIT_LENGTH);
+
+        self.0.set_bit(row * T::BIT_LENGTH + column, value);
+        self
+    }
+
+    /// Returns an iterator over the rows of the matrix, each given as a bit field whose bit `j` is
+    /// the entry in column `j`.
+    #[inline]
+    pub fn rows(&self) -> slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    /// Returns an iterator over the entries of column `column`, from the first row to the last.
+    ///
+    /// ```rust
+    /// use bit_field::BitMatrix;
+    ///
+    /// let matrix = BitMatrix([0b01u8, 0b10, 0b11]);
+    ///
+    /// assert!(matrix.column(1).eq([false, true, true]));
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `column` is out of bounds of the matrix.
+    #[track_caller]
+    #[inline]
+    pub fn column(&self, column: usize) -> Column<'_, T, ROWS> {
+        assert!(column < T::BIT_LENGTH);
+
+        Column {
+            matrix: self,
+            column,
+            rows: 0..ROWS,
+        }
+    }
+
+    /// Returns an iterator over the columns of the matrix, each given as an iterator over its
+    /// entries.
+    ///
+    /// ```rust
+    /// use bit_field::BitMatrix;
+    ///
+    /// let matrix = BitMatrix([0b01u8, 0b10, 0b11]);
+    ///
+    /// let weights: Vec<usize> = matrix.columns().map(|column| column.filter(|&bit| bit).count()).collect();
+    /// assert_eq!(weights, [2, 2, 0, 0, 0, 0, 0, 0]);
+    /// ```
+    #[inline]
+    pub fn columns(&self) -> Columns<'_, T, ROWS> {
+        Columns {
+            matrix: self,
+            columns: 0..T::BIT_LENGTH,
+        }
+    }
+
+    /// Multiplies the matrix with `other` over GF(2), where `other` must have one row for each
+    /// column of `self`.
+    ///
+    /// Row `i` of the product is the XOR of all rows `k` of `other` for which the entry in row `i`
+    /// and column `k` of `self` is `1`. The product is also available as the `*` operator.
+    ///
+    /// ```rust
+    /// use bit_field::BitMatrix;
+    ///
+    /// let a = BitMatrix([0b11u8, 0b01]);
+    /// let b = BitMatrix::<u16, 8>([0b110, 0b011, 0, 0, 0, 0, 0, 0]);
+    ///
+    /// assert_eq!(a.multiply(&b), BitMatrix([0b101, 0b110]));
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `K` is not the number of columns of `self`.
+    #[track_caller]
+    #[inline]
+    pub fn multiply<U, const K: usize>(&self, other: &BitMatrix<U, K>) -> BitMatrix<U, ROWS>
+    where
+        U: BitField + BitXor<Output = U> + Copy + Default,
+    {
+        assert!(
+            K == T::BIT_LENGTH,
+            "matrix dimensions do not match for multiplication"
+        );
+
+        let mut product = BitMatrix::default();
+        for (row, product_row) in self.0.iter().zip(product.0.iter_mut()) {
+            for (k, other_row) in other.0.iter().enumerate() {
+                if row.get_bit(k) {
+                    *product_row = *product_row ^ *other_row;
+                }
+            }
+        }
+        product
+    }
+}
+
+impl<T, U, const ROWS: usize, const K: usize> Mul<BitMatrix<U, K>> for BitMatrix<T, ROWS>
+where
+    T: BitField,
+    U: BitField + BitXor<Output = U> + Copy + Default,
+{
+    type Output = BitMatrix<U, ROWS>;
+
+    #[track_caller]
+    #[inline]
+    fn mul(self, other: BitMatrix<U, K>) -> BitMatrix<U, ROWS> {
+        self.multiply(&other)
+    }
+}
+
+/// An internal macro used for implementing the transpose of the square matrices of the standard
+/// unsigned integral types.
+macro_rules! bit_matrix_transpose_impl {
+    ($($t:ty => $n:expr),* $(,)*) => ($(
+        impl BitMatrix<$t, $n> {
+            /// Returns the transposed matrix, i.e. the entry in row `i` and column `j` of the
+            /// result is the entry in row `j` and column `i` of `self`.
+            ///
+            /// The matrix is transposed by recursively swapping the off-diagonal blocks of
+            /// half, quarter, and so on of its size, which takes log2(N) passes over the rows.
+            #[inline]
+            pub fn transpose(&self) -> Self {
+                let mut rows = self.0;
+                let mut width = $n / 2;
+                // the columns in the lower half of each block of twice the width
+                let mut mask: $t = !0 >> width;
+                while width != 0 {
+                    for block in (0..$n).step_by(2 * width) {
+                        for k in block..block + width {
+                            // swap the upper columns of row `k` with the lower columns of row
+                            // `k + width`
+                            let swapped = ((rows[k] >> width) ^ rows[k + width]) & mask;
+                            rows[k + width] ^= swapped;
+                            rows[k] ^= swapped << width;
+                        }
+                    }
+                    width /= 2;
+                    mask ^= mask << width;
+                }
+                BitMatrix(rows)
+            }
+        }
+    )*)
+}
+
+bit_matrix_transpose_impl! {
+    u8 => 8,
+    u16 => 16,
+    u32 => 32,
+    u64 => 64,
+    u128 => 128,
+}
+
+/// An iterator over the entries of a column of a `BitMatrix`, created by `BitMatrix::column`.
+#[derive(Debug, Clone)]
+pub struct Column<'a, T: 'a, const ROWS: usize> {
+    matrix: &'a BitMatrix<T, ROWS>,
+    column: usize,
+    rows: Range<usize>,
+}
+
+impl<'a, T: BitField, const ROWS: usize> Iterator for Column<'a, T, ROWS> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<bool> {
+        let row = self.rows.next()?;
+        Some(self.matrix.get(row, self.column))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<'a, T: BitField, const ROWS: usize> ExactSizeIterator for Column<'a, T, ROWS> {}
+
+/// An iterator over the columns of a `BitMatrix`, created by `BitMatrix::columns`.
+#[derive(Debug, Clone)]
+pub struct Columns<'a, T: 'a, const ROWS: usize> {
+    matrix: &'a BitMatrix<T, ROWS>,
+    columns: Range<usize>,
+}
+
+impl<'a, T: BitField, const ROWS: usize> Iterator for Columns<'a, T, ROWS> {
+    type Item = Column<'a, T, ROWS>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Column<'a, T, ROWS>> {
+        let column = self.columns.next()?;
+        Some(self.matrix.column(column))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.columns.size_hint()
+    }
+}
+
+impl<'a, T: BitField, const ROWS: usize> ExactSizeIterator for Columns<'a, T, ROWS> {}
@@ -0,0 +1,218 @@
+//! Morton (Z-order) encoding of 2D and 3D coordinates.
+
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+use mask::MaskOps;
+use BitField;
+
+/// Bit fields whose values can be interleaved in pairs into a Morton code of twice their width.
+pub trait Interleave2: BitField + Sized {
+    /// The bit field holding the interleaved bits of two values.
+    type Output: BitField;
+
+    /// Interleaves the bits of `x` and `y`, with the bits of `x` at the even positions of the
+    /// result and the bits of `y` at the odd positions.
+    fn interleave2(x: Self, y: Self) -> Self::Output;
+
+    /// Splits the Morton code `code` into the values whose bits are at its even and odd positions.
+    fn deinterleave2(code: Self::Output) -> (Self, Self);
+}
+
+/// Bit fields whose values can be interleaved in triples into a Morton code.
+pub trait Interleave3: BitField + Sized {
+    /// The bit field holding the interleaved bits of three values.
+    type Output: BitField;
+
+    /// Interleaves the bits of `x`, `y` and `z`, with bit `i` of `x` at position `3 * i` of the
+    /// result, bit `i` of `y` at position `3 * i + 1` and bit `i` of `z` at position `3 * i + 2`.
+    fn interleave3(x: Self, y: Self, z: Self) -> Self::Output;
+
+    /// Splits the Morton code `code` into the three values whose bits it interleaves.
+    fn deinterleave3(code: Self::Output) -> (Self, Self, Self);
+}
+
+/// Interleaves the bits of `x` and `y` into a Morton code, with the bits of `x` at the even
+/// positions and the bits of `y` at the odd positions.
+///
+/// ```rust
+/// use bit_field::interleave2;
+///
+/// assert_eq!(interleave2(0b1111u16, 0b0000), 0b0101_0101u32);
+/// assert_eq!(interleave2(0b0011u16, 0b0101), 0b0010_0111u32);
+/// ```
+#[inline]
+pub fn interleave2<T: Interleave2>(x: T, y: T) -> T::Output {
+    T::interleave2(x, y)
+}
+
+/// Splits a Morton code into the values whose bits are at its even and odd positions; this is the
+/// inverse of `interleave2`.
+///
+/// ```rust
+/// use bit_field::deinterleave2;
+///
+/// let (x, y): (u16, u16) = deinterleave2(0b0010_0111u32);
+///
+/// assert_eq!((x, y), (0b0011, 0b0101));
+/// ```
+#[inline]
+pub fn deinterleave2<T: Interleave2>(code: T::Output) -> (T, T) {
+    T::deinterleave2(code)
+}
+
+/// Interleaves the bits of `x`, `y` and `z` into a Morton code, with bit `i` of `x`, `y` and `z`
+/// at the positions `3 * i`, `3 * i + 1` and `3 * i + 2` respectively.
+///
+/// ```rust
+/// use bit_field::interleave3;
+///
+/// assert_eq!(interleave3(0b11u8, 0b01, 0b10), 0b101_011u32);
+/// ```
+#[inline]
+pub fn interleave3<T: Interleave3>(x: T, y: T, z: T) -> T::Output {
+    T::interleave3(x, y, z)
+}
+
+/// Splits a Morton code into the three values whose bits it interleaves; this is the inverse of
+/// `interleave3`.
+///
+/// ```rust
+/// use bit_field::deinterleave3;
+///
+/// let (x, y, z): (u8, u8, u8) = deinterleave3(0b101_011u32);
+///
+/// assert_eq!((x, y, z), (0b11, 0b01, 0b10));
+/// ```
+#[inline]
+pub fn deinterleave3<T: Interleave3>(code: T::Output) -> (T, T, T) {
+    T::deinterleave3(code)
+}
+
+/// An internal macro used for spreading the bits of a value to every second or third bit of a
+/// wider value, and for gathering them back.
+///
+/// With BMI2 available this uses the `PDEP` and `PEXT` instructions; otherwise the bits are moved
+/// by a sequence of shifts and masks, each halving the distance the bits still have to travel.
+macro_rules! morton_spread_impl {
+    ($(
+        $spread:ident, $compact:ident: $t:ty => $out:ty, $mask:expr,
+        spread [$(($spread_shift:expr, $spread_mask:expr)),*],
+        compact [$(($compact_shift:expr, $compact_mask:expr)),*];
+    )*) => ($(
+        #[inline]
+        fn $spread(value: $t) -> $out {
+            #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+            {
+                MaskOps::deposit(value as u64, $mask as u64) as $out
+            }
+            #[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+            {
+                let mut bits = value as $out;
+                $(bits = (bits | bits << $spread_shift) & $spread_mask;)*
+                bits
+            }
+        }
+
+        #[inline]
+        fn $compact(code: $out) -> $t {
+            #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+            {
+                MaskOps::extract(code as u64, $mask as u64) as $t
+            }
+            #[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+            {
+                let mut bits = code & $mask;
+                $(bits = (bits | bits >> $compact_shift) & $compact_mask;)*
+                bits as $t
+            }
+        }
+    )*)
+}
+
+morton_spread_impl! {
+    spread2_u8, compact2_u8: u8 => u16, 0x5555,
+        spread [(4, 0x0f0f), (2, 0x3333), (1, 0x5555)],
+        compact [(1, 0x3333), (2, 0x0f0f), (4, 0x00ff)];
+    spread2_u16, compact2_u16: u16 => u32, 0x5555_5555,
+        spread [(8, 0x00ff_00ff), (4, 0x0f0f_0f0f), (2, 0x3333_3333), (1, 0x5555_5555)],
+        compact [(1, 0x3333_3333), (2, 0x0f0f_0f0f), (4, 0x00ff_00ff), (8, 0x0000_ffff)];
+    spread2_u32, compact2_u32: u32 => u64, 0x5555_5555_5555_5555,
+        spread [
+            (16, 0x0000_ffff_0000_ffff),
+            (8, 0x00ff_00ff_00ff_00ff),
+            (4, 0x0f0f_0f0f_0f0f_0f0f),
+            (2, 0x3333_3333_3333_3333),
+            (1, 0x5555_5555_5555_5555)
+        ],
+        compact [
+            (1, 0x3333_3333_3333_3333),
+            (2, 0x0f0f_0f0f_0f0f_0f0f),
+            (4, 0x00ff_00ff_00ff_00ff),
+            (8, 0x0000_ffff_0000_ffff),
+            (16, 0x0000_0000_ffff_ffff)
+        ];
+    spread3_u8, compact3_u8: u8 => u32, 0x0024_9249,
+        spread [(8, 0x0000_f00f), (4, 0x000c_30c3), (2, 0x0024_9249)],
+        compact [(2, 0x000c_30c3), (4, 0x0000_f00f), (8, 0x0000_00ff)];
+    spread3_u16, compact3_u16: u16 => u64, 0x0000_2492_4924_9249,
+        spread [
+            (16, 0x0000_0000_ff00_00ff),
+            (8, 0x000f_00f0_0f00_f00f),
+            (4, 0x00c3_0c30_c30c_30c3),
+            (2, 0x0000_2492_4924_9249)
+        ],
+        compact [
+            (2, 0x00c3_0c30_c30c_30c3),
+            (4, 0x000f_00f0_0f00_f00f),
+            (8, 0x0000_0000_ff00_00ff),
+            (16, 0x0000_0000_0000_ffff)
+        ];
+}
+
+/// An internal macro used for implementing Interleave2 on the unsigned integral types.
+macro_rules! interleave2_impl {
+    ($($t:ty => $out:ty, $spread:ident, $compact:ident;)*) => ($(
+        impl Interleave2 for $t {
+            type Output = $out;
+
+            #[inline]
+            fn interleave2(x: Self, y: Self) -> $out {
+                $spread(x) | $spread(y) << 1
+            }
+
+            #[inline]
+            fn deinterleave2(code: $out) -> (Self, Self) {
+                ($compact(code), $compact(code >> 1))
+            }
+        }
+    )*)
+}
+
+interleave2_impl! {
+    u8 => u16, spread2_u8, compact2_u8;
+    u16 => u32, spread2_u16, compact2_u16;
+    u32 => u64, spread2_u32, compact2_u32;
+}
+
+/// An internal macro used for implementing Interleave3 on the unsigned integral types.
+macro_rules! interleave3_impl {
+    ($($t:ty => $out:ty, $spread:ident, $compact:ident;)*) => ($(
+        impl Interleave3 for $t {
+            type Output = $out;
+
+            #[inline]
+            fn interleave3(x: Self, y: Self, z: Self) -> $out {
+                $spread(x) | $spread(y) << 1 | $spread(z) << 2
+            }
+
+            #[inline]
+            fn deinterleave3(code: $out) -> (Self, Self, Self) {
+                ($compact(code), $compact(code >> 1), $compact(code >> 2))
+            }
+        }
+    )*)
+}
+
+interleave3_impl! {
+    u8 => u32, spread3_u8, compact3_u8;
+    u16 => u64, spread3_u16, compact3_u16;
+}
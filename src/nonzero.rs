@@ -0,0 +1,173 @@
+use core::fmt;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use core::ops::RangeBounds;
+
+use BitField;
+
+/// The error returned when an update of a `NonZero*` value would make it zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroError;
+
+impl fmt::Display for ZeroError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("bit update would make a non-zero value zero")
+    }
+}
+
+impl core::error::Error for ZeroError {}
+
+/// A companion trait to `BitField` for the `NonZero*` integer types.
+///
+/// Reading works like on the underlying integer type. Since a `NonZero*` value must never become
+/// zero, the updating methods are checked and leave the value unchanged if the update would
+/// clear all of its bits.
+pub trait NonZeroBitField: Sized {
+    /// The integer type wrapped by this non-zero type.
+    type Bits: BitField;
+
+    /// Obtains the bit at the index `bit`; note that index 0 is the least significant bit, while
+    /// index `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::NonZeroBitField;
+    /// use std::num::NonZeroU32;
+    ///
+    /// let value = NonZeroU32::new(0b110101).unwrap();
+    ///
+    /// assert_eq!(value.get_bit(1), false);
+    /// assert_eq!(value.get_bit(2), true);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit field.
+    fn get_bit(&self, bit: usize) -> bool;
+
+    /// Obtains the range of bits specified by `range`; note that index 0 is the least significant
+    /// bit, while index `length() - 1` is the most significant bit.
+    ///
+    /// The extracted bits are returned as the underlying integer type, since they may be zero.
+    ///
+    /// ```rust
+    /// use bit_field::NonZeroBitField;
+    /// use std::num::NonZeroU32;
+    ///
+    /// let value = NonZeroU32::new(0b110101).unwrap();
+    ///
+    /// assert_eq!(value.get_bits(0..3), 0b101);
+    /// assert_eq!(value.get_bits(6..), 0);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit field.
+    fn get_bits<T: RangeBounds<usize>>(&self, range: T) -> Self::Bits;
+
+    /// Sets the bit at the index `bit` to the value `value`, unless this would make the value
+    /// zero, in which case `ZeroError` is returned and the value is left unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::{NonZeroBitField, ZeroError};
+    /// use std::num::NonZeroU8;
+    ///
+    /// let mut value = NonZeroU8::new(0b100).unwrap();
+    ///
+    /// value.try_set_bit(0, true).unwrap();
+    /// assert_eq!(value.get(), 0b101);
+    ///
+    /// value.try_set_bit(2, false).unwrap();
+    /// assert_eq!(value.try_set_bit(0, false), Err(ZeroError));
+    /// assert_eq!(value.get(), 0b001);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of the bounds of the bit field.
+    fn try_set_bit(&mut self, bit: usize, value: bool) -> Result<&mut Self, ZeroError>;
+
+    /// Sets the range of bits defined by the range `range` to the lower bits of `value`, unless
+    /// this would make the value zero, in which case `ZeroError` is returned and the value is left
+    /// unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::{NonZeroBitField, ZeroError};
+    /// use std::num::NonZeroU16;
+    ///
+    /// let mut value = NonZeroU16::new(0x0f0).unwrap();
+    ///
+    /// value.try_set_bits(8..12, 0xa).unwrap();
+    /// assert_eq!(value.get(), 0xaf0);
+    ///
+    /// value.try_set_bits(4..8, 0).unwrap();
+    /// assert_eq!(value.try_set_bits(8..12, 0), Err(ZeroError));
+    /// assert_eq!(value.get(), 0xa00);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `value`.
+    fn try_set_bits<T: RangeBounds<usize>>(
+        &mut self,
+        range: T,
+        value: Self::Bits,
+    ) -> Result<&mut Self, ZeroError>;
+}
+
+/// An internal macro used for implementing NonZeroBitField on the standard non-zero types.
+macro_rules! nonzero_bitfield_impl {
+    ($($t:ident => $bits:ty),* $(,)*) => ($(
+        impl NonZeroBitField for $t {
+            type Bits = $bits;
+
+            #[track_caller]
+            #[inline]
+            fn get_bit(&self, bit: usize) -> bool {
+                self.get().get_bit(bit)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn get_bits<T: RangeBounds<usize>>(&self, range: T) -> $bits {
+                self.get().get_bits(range)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn try_set_bit(&mut self, bit: usize, value: bool) -> Result<&mut Self, ZeroError> {
+                *self = $t::new(*self.get().set_bit(bit, value)).ok_or(ZeroError)?;
+                Ok(self)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn try_set_bits<T: RangeBounds<usize>>(
+                &mut self,
+                range: T,
+                value: $bits,
+            ) -> Result<&mut Self, ZeroError> {
+                *self = $t::new(*self.get().set_bits(range, value)).ok_or(ZeroError)?;
+                Ok(self)
+            }
+        }
+    )*)
+}
+
+nonzero_bitfield_impl! {
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize,
+}
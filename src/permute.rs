@@ -0,0 +1,175 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use BitField;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The integral types that a `BitPermutation` can read from and write to, and that
+/// `BitArray::get_bits_as` and `BitArray::set_bits_as` convert between.
+///
+/// The bits are moved within a `u128`, so conversions are plain bit pattern casts that never fail.
+/// This trait is sealed: it is implemented for all primitive integral types and can't be
+/// implemented outside of this crate, but it can be named as a bound by generic code.
+///
+/// ```rust
+/// use bit_field::{BitPermutation, PermutationWord};
+///
+/// fn apply_all<T: PermutationWord, U: PermutationWord>(
+///     permutation: &BitPermutation<T, U>,
+///     values: &[T],
+/// ) -> Vec<U> {
+///     values.iter().map(|&value| permutation.apply(value)).collect()
+/// }
+///
+/// let swap = BitPermutation::<u8, u8>::new(&[1, 0]);
+/// assert_eq!(apply_all(&swap, &[0b01, 0b10, 0b11]), [0b10, 0b01, 0b11]);
+/// ```
+pub trait PermutationWord: sealed::Sealed + BitField + Copy {
+    /// Returns the bit pattern of `self` in the lower bits of a `u128`.
+    fn to_bits(self) -> u128;
+
+    /// Returns the value with the bit pattern of the lower bits of `bits`.
+    fn from_bits(bits: u128) -> Self;
+}
+
+/// An internal macro used for implementing PermutationWord on the standard integral types.
+macro_rules! permutation_word_impl {
+    ($($t:ty => $unsigned:ty),* $(,)*) => ($(
+        impl sealed::Sealed for $t {}
+
+        impl PermutationWord for $t {
+            #[inline]
+            fn to_bits(self) -> u128 {
+                self as $unsigned as u128
+            }
+
+            #[inline]
+            fn from_bits(bits: u128) -> Self {
+                bits as $t
+            }
+        }
+    )*)
+}
+
+permutation_word_impl! {
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    u128 => u128,
+    usize => usize,
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize,
+}
+
+/// A precomputed permutation of the bits of a `T` into a `U`, where bit `i` of the output is bit
+/// `table[i]` of the input.
+///
+/// Input bits may be used several times or not at all, so the permutation can also expand or
+/// compress values, like the expansion and compression permutations of DES. On construction, the
+/// table is compiled into a shift network: all bits that move by the same distance are moved
+/// together by a single mask and shift.
+///
+/// ```rust
+/// use bit_field::BitPermutation;
+///
+/// // expand each 4-bit group into 6 bits by repeating the neighboring bits of the previous and
+/// // next group, like the expansion permutation of DES
+/// let expansion = BitPermutation::<u8, u16>::new(&[7, 0, 1, 2, 3, 4, 3, 4, 5, 6, 7, 0]);
+///
+/// assert_eq!(expansion.apply(0b1000_0001), 0b1100_0000_0011);
+/// ```
+pub struct BitPermutation<T, U> {
+    masks: [u128; 128],
+    shifts: [i8; 128],
+    len: usize,
+    types: PhantomData<fn(T) -> U>,
+}
+
+impl<T: PermutationWord, U: PermutationWord> BitPermutation<T, U> {
+    /// Compiles the permutation in which bit `i` of the output is bit `table[i]` of the input; all
+    /// output bits from `N` on are `0`.
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if `N` is larger than the bit length of `U`, or if any index in
+    /// `table` is out of bounds of `T`.
+    #[track_caller]
+    pub fn new<const N: usize>(table: &[u8; N]) -> Self {
+        assert!(
+            N <= U::BIT_LENGTH,
+            "permutation table is longer than the output"
+        );
+
+        let mut permutation = BitPermutation {
+            masks: [0; 128],
+            shifts: [0; 128],
+            len: 0,
+            types: PhantomData,
+        };
+        for (bit, &source) in table.iter().enumerate() {
+            assert!(
+                (source as usize) < T::BIT_LENGTH,
+                "permutation index is out of bounds of the input"
+            );
+
+            // output and input bits are below 128, so the shift always fits into an `i8`
+            let shift = (bit as isize - source as isize) as i8;
+            let group = match permutation.shifts[..permutation.len]
+                .iter()
+                .position(|&group_shift| group_shift == shift)
+            {
+                Some(group) => group,
+                None => {
+                    permutation.len += 1;
+                    permutation.shifts[permutation.len - 1] = shift;
+                    permutation.len - 1
+                }
+            };
+            permutation.masks[group] |= 1 << source;
+        }
+        permutation
+    }
+
+    /// Applies the permutation to `value`.
+    #[inline]
+    pub fn apply(&self, value: T) -> U {
+        let bits = value.to_bits();
+        let permuted = self.masks[..self.len]
+            .iter()
+            .zip(&self.shifts[..self.len])
+            .fold(0, |permuted, (&mask, &shift)| {
+                if shift >= 0 {
+                    permuted | (bits & mask) << shift
+                } else {
+                    permuted | (bits & mask) >> -shift
+                }
+            });
+        U::from_bits(permuted)
+    }
+}
+
+impl<T, U> Clone for BitPermutation<T, U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, U> Copy for BitPermutation<T, U> {}
+
+impl<T, U> fmt::Debug for BitPermutation<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitPermutation")
+            .field("masks", &&self.masks[..self.len])
+            .field("shifts", &&self.shifts[..self.len])
+            .finish()
+    }
+}
@@ -0,0 +1,294 @@
+use core::ops::{Bound, Range, RangeBounds};
+
+use {to_regular_range, BitField};
+
+/// A half-open range of bit indexes `start..end`.
+///
+/// Unlike `Range<usize>`, a `BitRange` is `Copy` and provides set-like operations on ranges. It
+/// implements `RangeBounds<usize>`, so it can be passed to `get_bits` and `set_bits` directly.
+///
+/// ```rust
+/// use bit_field::{BitField, BitRange};
+///
+/// let low = BitRange::new(0, 4);
+/// let high = low.shift(4);
+///
+/// let mut value = 0u8;
+/// value.set_bits(high, 0b1010);
+/// assert_eq!(value.get_bits(low.union_if_contiguous(high).unwrap()), 0b1010_0000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BitRange {
+    start: usize,
+    end: usize,
+}
+
+impl BitRange {
+    /// Creates the range `start..end`.
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if `start` is larger than `end`.
+    #[track_caller]
+    #[inline]
+    pub const fn new(start: usize, end: usize) -> BitRange {
+        assert!(start <= end);
+
+        BitRange { start, end }
+    }
+
+    /// Creates the range `start..end` if it is a valid range for the bit field `T`, i.e. if
+    /// `get_bits` and `set_bits` would accept it.
+    ///
+    /// ```rust
+    /// use bit_field::BitRange;
+    ///
+    /// assert_eq!(BitRange::checked::<u8>(2, 8), Some(BitRange::new(2, 8)));
+    /// assert_eq!(BitRange::checked::<u8>(2, 9), None);
+    /// assert_eq!(BitRange::checked::<u8>(4, 2), None);
+    /// ```
+    #[inline]
+    pub fn checked<T: BitField>(start: usize, end: usize) -> Option<BitRange> {
+        if start <= end && start < T::BIT_LENGTH && end <= T::BIT_LENGTH {
+            Some(BitRange { start, end })
+        } else {
+            None
+        }
+    }
+
+    /// Converts any range of bit indexes into a `BitRange` if it is a valid range for the bit
+    /// field `T`; unbounded ends are resolved against `T::BIT_LENGTH`.
+    ///
+    /// ```rust
+    /// use bit_field::BitRange;
+    ///
+    /// assert_eq!(BitRange::from_bounds::<u16, _>(4..), Some(BitRange::new(4, 16)));
+    /// assert_eq!(BitRange::from_bounds::<u16, _>(..=15), Some(BitRange::new(0, 16)));
+    /// assert_eq!(BitRange::from_bounds::<u16, _>(..=16), None);
+    /// ```
+    #[inline]
+    pub fn from_bounds<T: BitField, R: RangeBounds<usize>>(range: R) -> Option<BitRange> {
+        let range = to_regular_range(&range, T::BIT_LENGTH);
+        BitRange::checked::<T>(range.start, range.end)
+    }
+
+    /// Returns the index of the first bit in the range.
+    #[inline]
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the index one past the last bit in the range.
+    #[inline]
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the number of bits in the range.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns whether the range contains no bits.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns whether the bit index `bit` lies within the range.
+    ///
+    /// ```rust
+    /// use bit_field::BitRange;
+    ///
+    /// assert!(BitRange::new(4, 8).contains(4));
+    /// assert!(!BitRange::new(4, 8).contains(8));
+    /// ```
+    #[inline]
+    pub const fn contains(&self, bit: usize) -> bool {
+        self.start <= bit && bit < self.end
+    }
+
+    /// Returns a value of the bit field `T` with all bits in the range set to `1` and all other
+    /// bits set to `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitRange;
+    ///
+    /// assert_eq!(BitRange::new(4, 12).mask::<u16>(), 0x0ff0);
+    /// assert_eq!(BitRange::new(0, 32).mask::<u32>(), u32::MAX);
+    /// assert_eq!(BitRange::new(3, 3).mask::<u8>(), 0);
+    /// assert_eq!(BitRange::new(0, 4).mask::<i8>(), 0x0f);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field `T`.
+    #[track_caller]
+    #[inline]
+    pub fn mask<T: BitField + Default>(&self) -> T {
+        // set the bits one by one, as `get_bits` sign-extends the all-ones value for signed types
+        let mut mask = T::default();
+        for bit in self.iter() {
+            mask.set_bit(bit, true);
+        }
+        mask
+    }
+
+    /// Returns the bits that lie in both ranges, or `None` if the ranges have no bits in common.
+    ///
+    /// ```rust
+    /// use bit_field::BitRange;
+    ///
+    /// let range = BitRange::new(4, 12);
+    ///
+    /// assert_eq!(range.intersect(BitRange::new(8, 16)), Some(BitRange::new(8, 12)));
+    /// assert_eq!(range.intersect(BitRange::new(12, 16)), None);
+    /// ```
+    #[inline]
+    pub fn intersect(&self, other: BitRange) -> Option<BitRange> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        if start < end {
+            Some(BitRange { start, end })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the range covering the bits of both ranges if together they form a single
+    /// contiguous range, i.e. if they overlap or are adjacent, and `None` otherwise.
+    ///
+    /// ```rust
+    /// use bit_field::BitRange;
+    ///
+    /// let range = BitRange::new(4, 8);
+    ///
+    /// assert_eq!(range.union_if_contiguous(BitRange::new(8, 12)), Some(BitRange::new(4, 12)));
+    /// assert_eq!(range.union_if_contiguous(BitRange::new(2, 6)), Some(BitRange::new(2, 8)));
+    /// assert_eq!(range.union_if_contiguous(BitRange::new(9, 12)), None);
+    /// ```
+    #[inline]
+    pub fn union_if_contiguous(&self, other: BitRange) -> Option<BitRange> {
+        if self.is_empty() {
+            Some(other)
+        } else if other.is_empty() {
+            Some(*self)
+        } else if self.start <= other.end && other.start <= self.end {
+            Some(BitRange {
+                start: self.start.min(other.start),
+                end: self.end.max(other.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the range moved by `n` bits; a positive `n` moves it towards the most significant
+    /// bit and a negative `n` towards the least significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitRange;
+    ///
+    /// assert_eq!(BitRange::new(4, 8).shift(4), BitRange::new(8, 12));
+    /// assert_eq!(BitRange::new(4, 8).shift(-4), BitRange::new(0, 4));
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start of the range would become negative or the end would
+    /// overflow `usize`.
+    #[track_caller]
+    #[inline]
+    pub fn shift(&self, n: isize) -> BitRange {
+        let shift = |index: usize| {
+            index
+                .checked_add_signed(n)
+                .expect("shifted bit range is out of bounds")
+        };
+        BitRange {
+            start: shift(self.start),
+            end: shift(self.end),
+        }
+    }
+
+    /// Splits the range at the bit index `bit` into the ranges `start..bit` and `bit..end`.
+    ///
+    /// ```rust
+    /// use bit_field::BitRange;
+    ///
+    /// assert_eq!(
+    ///     BitRange::new(4, 12).split_at(10),
+    ///     (BitRange::new(4, 10), BitRange::new(10, 12))
+    /// );
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `bit` is not within `start..=end`.
+    #[track_caller]
+    #[inline]
+    pub fn split_at(&self, bit: usize) -> (BitRange, BitRange) {
+        assert!(self.start <= bit && bit <= self.end);
+
+        (
+            BitRange {
+                start: self.start,
+                end: bit,
+            },
+            BitRange {
+                start: bit,
+                end: self.end,
+            },
+        )
+    }
+
+    /// Returns an iterator over the bit indexes in the range.
+    ///
+    /// ```rust
+    /// use bit_field::BitRange;
+    ///
+    /// assert!(BitRange::new(2, 5).iter().eq([2, 3, 4]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl RangeBounds<usize> for BitRange {
+    #[inline]
+    fn start_bound(&self) -> Bound<&usize> {
+        Bound::Included(&self.start)
+    }
+
+    #[inline]
+    fn end_bound(&self) -> Bound<&usize> {
+        Bound::Excluded(&self.end)
+    }
+}
+
+impl From<Range<usize>> for BitRange {
+    #[track_caller]
+    #[inline]
+    fn from(range: Range<usize>) -> BitRange {
+        BitRange::new(range.start, range.end)
+    }
+}
+
+impl From<BitRange> for Range<usize> {
+    #[inline]
+    fn from(range: BitRange) -> Range<usize> {
+        range.start..range.end
+    }
+}
+
+impl IntoIterator for BitRange {
+    type Item = usize;
+    type IntoIter = Range<usize>;
+
+    #[inline]
+    fn into_iter(self) -> Range<usize> {
+        self.iter()
+    }
+}
@@ -0,0 +1,116 @@
+use mask::RangeMask;
+use BitField;
+
+/// An iterator over all submasks of a mask, i.e. all values whose set bits are a subset of the set
+/// bits of the mask, in descending order from the mask itself down to `0`.
+///
+/// ```rust
+/// use bit_field::Submasks;
+///
+/// assert!(Submasks::new(0b1010u8).eq([0b1010, 0b1000, 0b0010, 0b0000]));
+/// assert_eq!(Submasks::new(0xffffu16).count(), 1 << 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Submasks<T> {
+    next: Option<T>,
+    mask: T,
+}
+
+impl<T: BitField + Copy> Submasks<T> {
+    /// Creates an iterator over all submasks of `mask`.
+    #[inline]
+    pub fn new(mask: T) -> Self {
+        Submasks {
+            next: Some(mask),
+            mask,
+        }
+    }
+}
+
+/// An iterator over all values with exactly `k` of their lower `n` bits set, in ascending order.
+///
+/// The values are computed with Gosper's hack, which also works if the combinations reach the most
+/// significant bit of the type.
+///
+/// ```rust
+/// use bit_field::Combinations;
+///
+/// assert!(Combinations::<u8>::new(4, 2).eq([0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]));
+/// assert_eq!(Combinations::<u64>::new(64, 2).count(), 64 * 63 / 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Combinations<T> {
+    next: Option<T>,
+    last: T,
+}
+
+impl<T: BitField + Default> Combinations<T> {
+    /// Creates an iterator over all values with exactly `k` of their lower `n` bits set.
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if `k` is larger than `n`, or if `n` is larger than the bit
+    /// length of the type.
+    #[track_caller]
+    #[inline]
+    pub fn new(n: usize, k: usize) -> Self {
+        assert!(k <= n);
+        assert!(n <= T::BIT_LENGTH);
+
+        let (mut first, mut last) = (T::default(), T::default());
+        for bit in 0..k {
+            first.set_bit(bit, true);
+            last.set_bit(n - k + bit, true);
+        }
+        Combinations {
+            next: Some(first),
+            last,
+        }
+    }
+}
+
+/// An internal macro used for implementing the subset iterators on the standard integral types.
+macro_rules! subsets_impl {
+    ($($t:ty)*) => ($(
+        impl Iterator for Submasks<$t> {
+            type Item = $t;
+
+            #[inline]
+            fn next(&mut self) -> Option<$t> {
+                let submask = self.next?;
+                self.next = if submask == 0 {
+                    None
+                } else {
+                    Some(submask.wrapping_sub(1) & self.mask)
+                };
+                Some(submask)
+            }
+        }
+
+        impl Iterator for Combinations<$t> {
+            type Item = $t;
+
+            #[inline]
+            fn next(&mut self) -> Option<$t> {
+                let combination = self.next?;
+                self.next = if combination == self.last {
+                    None
+                } else {
+                    // move the lowest block of ones up by one bit, then refill the lowest bits
+                    // with the remaining ones of the block; since this is not the last
+                    // combination, the carry never leaves the value
+                    let lowest = combination & combination.wrapping_neg();
+                    let carried = combination.wrapping_add(lowest);
+                    let shift = lowest.trailing_zeros() as usize + 2;
+                    // mask the shifted value, so that the shift is a logical one for signed types
+                    let refill = (combination ^ carried).checked_shr(shift as u32).unwrap_or(0)
+                        & <$t>::range_mask(0..<$t>::BIT_LENGTH - shift);
+                    Some(carried | refill)
+                };
+                Some(combination)
+            }
+        }
+    )*)
+}
+
+subsets_impl! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
@@ -457,3 +457,2100 @@ fn test_get_bits_array() {
     test_array = [0x3f, 0x80, 0xaa];
     assert_eq!(test_array.get_bits(6..14), 0x00);
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn test_bit_reader() {
+    use std::io::ErrorKind;
+    use BitReader;
+
+    let data = [0xa5u8, 0x0f, 0xff, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc];
+    let mut reader = BitReader::new(&data[..]);
+
+    assert!(reader.read_bit().unwrap());
+    assert!(!reader.read_bit().unwrap());
+    assert_eq!(reader.read_bits(6).unwrap(), 0b101001);
+    assert!(reader.is_aligned());
+    assert_eq!(reader.read_bits(12).unwrap(), 0xf0f);
+    assert_eq!(reader.read_bits(0).unwrap(), 0);
+    reader.align();
+    assert_eq!(reader.read_bits(16).unwrap(), 0x3412);
+    assert_eq!(reader.read_bits(28).unwrap(), 0xc9a7856);
+    assert_eq!(reader.read_bits(4).unwrap(), 0xb);
+    assert_eq!(
+        reader.read_bit().unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+
+    let data = [0xffu8; 200];
+    let mut reader = BitReader::new(&data[..]);
+    for _ in 0..25 {
+        assert_eq!(reader.read_bits(64).unwrap(), u64::MAX);
+    }
+    assert!(reader.read_bits(1).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_bit_writer() {
+    use std::vec::Vec;
+    use BitReader;
+    use BitWriter;
+
+    let mut writer = BitWriter::new(Vec::new());
+    writer.write_bit(true).unwrap();
+    writer.write_bit(false).unwrap();
+    writer.write_bits(6, 0b101001).unwrap();
+    assert!(writer.is_aligned());
+    writer.write_bits(12, 0xf0f).unwrap();
+    writer.write_bits(0, 0).unwrap();
+    writer.align().unwrap();
+    writer.write_bits(16, 0x3412).unwrap();
+    writer.write_bits(28, 0xc9a7856).unwrap();
+    writer.write_bits(3, 0b100).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().len(), 8);
+    assert_eq!(
+        writer.into_inner().unwrap(),
+        [0xa5, 0x0f, 0x0f, 0x12, 0x34, 0x56, 0x78, 0x9a, 0x4c]
+    );
+
+    let mut writer = BitWriter::new(Vec::new());
+    for i in 0..1000u64 {
+        writer.write_bits(11, i).unwrap();
+    }
+    let data = writer.into_inner().unwrap();
+    assert_eq!(data.len(), 1375);
+    let mut reader = BitReader::new(&data[..]);
+    for i in 0..1000u64 {
+        assert_eq!(reader.read_bits(11).unwrap(), i);
+    }
+
+    let mut data = Vec::new();
+    {
+        let mut writer = BitWriter::new(&mut data);
+        writer.write_bits(11, 0x5a5).unwrap();
+    }
+    assert_eq!(data, [0xa5, 0x05]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_bit_stream_codes() {
+    use std::vec::Vec;
+    use BitReader;
+    use BitWriter;
+
+    let unsigned = [
+        0,
+        1,
+        2,
+        3,
+        4,
+        7,
+        8,
+        1000,
+        1 << 32,
+        u64::MAX >> 1,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+    let signed = [0, 1, -1, 2, -2, 1000, -1000, i64::MAX, i64::MIN + 1];
+
+    let mut writer = BitWriter::new(Vec::new());
+    for &value in &unsigned {
+        writer.write_unary(value % 200).unwrap();
+        writer.write_ue(value).unwrap();
+        for k in 0..64 {
+            writer
+                .write_rice(k, value >> 56usize.saturating_sub(k))
+                .unwrap();
+        }
+    }
+    for &value in &signed {
+        writer.write_se(value).unwrap();
+    }
+    writer.write_unary(3000).unwrap();
+    let data = writer.into_inner().unwrap();
+
+    let mut reader = BitReader::new(&data[..]);
+    for &value in &unsigned {
+        assert_eq!(reader.read_unary().unwrap(), value % 200);
+        assert_eq!(reader.read_ue().unwrap(), value);
+        for k in 0..64 {
+            assert_eq!(
+                reader.read_rice(k).unwrap(),
+                value >> 56usize.saturating_sub(k)
+            );
+        }
+    }
+    for &value in &signed {
+        assert_eq!(reader.read_se().unwrap(), value);
+    }
+    assert_eq!(reader.read_unary().unwrap(), 3000);
+
+    let mut writer = BitWriter::new(Vec::new());
+    writer.write_ue(3).unwrap();
+    writer.write_se(-2).unwrap();
+    writer.write_rice(2, 9).unwrap();
+    assert_eq!(writer.into_inner().unwrap(), [0b1000_0100, 0b0011_0001]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_bit_stream_codes_malformed() {
+    use std::io::ErrorKind;
+    use BitReader;
+
+    // a prefix of 65 zeros
+    let data = [0u8, 0, 0, 0, 0, 0, 0, 0, 0b10];
+    let error = BitReader::new(&data[..]).read_ue().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    // a prefix of 64 zeros with a non-zero suffix overflows a `u64`
+    let data = [0u8, 0, 0, 0, 0, 0, 0, 0, 0b11, 0, 0, 0, 0, 0, 0, 0, 0];
+    let error = BitReader::new(&data[..]).read_ue().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    let data = [0u8, 0, 0, 0, 0, 0, 0, 0, 0b01, 0, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(BitReader::new(&data[..]).read_ue().unwrap(), u64::MAX);
+    let error = BitReader::new(&data[..]).read_se().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    // the quotient of 16 doesn't fit above the lower 60 bits
+    let data = [0u8, 0, 0b1, 0, 0, 0, 0, 0, 0, 0];
+    let error = BitReader::new(&data[..]).read_rice(60).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(BitReader::new(&data[..]).read_rice(59).unwrap(), 16 << 59);
+
+    let data = [0u8; 3];
+    let error = BitReader::new(&data[..]).read_unary().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    let data = [0b1000_0000u8];
+    let error = BitReader::new(&data[..]).read_ue().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic(expected = "value does not fit into bit count")]
+fn test_bit_writer_value_too_large() {
+    use std::vec::Vec;
+    use BitWriter;
+
+    let mut writer = BitWriter::new(Vec::new());
+    let _ = writer.write_bits(3, 0b1000);
+}
+
+#[test]
+fn test_float_fields() {
+    use FloatBitField;
+
+    let value = -6.5f32;
+    assert!(value.sign());
+    assert_eq!(value.exponent(), 129);
+    assert_eq!(value.fraction(), 0b101 << 20);
+
+    let mut value = 6.5f64;
+    value.set_sign(true);
+    assert_eq!(value, -6.5);
+    value.set_exponent(1023);
+    assert_eq!(value, -1.625);
+    value.set_fraction(0);
+    assert_eq!(value, -1.0);
+
+    assert_eq!(f32::INFINITY.exponent(), 0xff);
+    assert_eq!(f32::INFINITY.fraction(), 0);
+    assert_eq!(f64::NEG_INFINITY.exponent(), 0x7ff);
+    assert!(f64::NEG_INFINITY.sign());
+}
+
+#[test]
+fn test_float_nan_payload() {
+    use FloatBitField;
+
+    let mut value = f32::NAN;
+    value.set_fraction(1 << 22 | 0x1234);
+    assert!(value.is_nan());
+    assert_eq!(value.fraction(), 1 << 22 | 0x1234);
+    assert_eq!(value.get_bits(0..16), 0x1234);
+
+    // clearing the quiet bit keeps a non-zero payload, so this is a signaling NaN
+    value.set_bit(22, false);
+    assert!(value.is_nan());
+    assert_eq!(value.fraction(), 0x1234);
+
+    // without any payload bits, the pattern becomes an infinity
+    value.set_fraction(0);
+    assert_eq!(value, f32::INFINITY);
+
+    let mut value = f64::NAN;
+    value.set_sign(true).set_fraction(0xdead_beef);
+    assert!(value.is_nan());
+    assert!(value.sign());
+    assert_eq!(value.fraction(), 0xdead_beef);
+    assert_eq!(value.to_bits(), 0xfff0_0000_dead_beef);
+}
+
+#[test]
+fn test_float_subnormals() {
+    use FloatBitField;
+
+    let smallest = f32::from_bits(1);
+    assert!(smallest.is_subnormal());
+    assert_eq!(smallest.exponent(), 0);
+    assert_eq!(smallest.fraction(), 1);
+
+    let mut value = 0.0f32;
+    value.set_fraction(1);
+    assert_eq!(value, smallest);
+    value.set_bits(0..23, 0x7f_ffff);
+    assert!(value.is_subnormal());
+    assert!(value < f32::MIN_POSITIVE);
+    value.set_exponent(1);
+    assert_eq!(value.fraction(), 0x7f_ffff);
+    assert!(value.is_normal());
+
+    let mut value = f64::MIN_POSITIVE;
+    assert_eq!(value.exponent(), 1);
+    value.set_exponent(0);
+    assert_eq!(value, 0.0);
+    value.set_bit(51, true);
+    assert!(value.is_subnormal());
+    assert_eq!(value, f64::MIN_POSITIVE / 2.0);
+}
+
+#[test]
+fn test_wrapper_types() {
+    use core::num::{Saturating, Wrapping};
+
+    assert_eq!(Wrapping::<u32>::BIT_LENGTH, 32);
+    assert_eq!(Saturating::<i16>::BIT_LENGTH, 16);
+
+    let mut counter = Wrapping(0xfffu32);
+    assert!(counter.get_bit(11));
+    assert_eq!(counter.get_bits(4..8), Wrapping(0xf));
+    counter.set_bits(8..12, Wrapping(0x5));
+    assert_eq!(counter, Wrapping(0x5ff));
+    counter += Wrapping(u32::MAX);
+    assert_eq!(counter.get_bits(..), Wrapping(0x5fe));
+
+    let mut value = Saturating(0u8);
+    value.set_bit(7, true).set_bits(0..2, Saturating(0b11));
+    assert_eq!(value, Saturating(0x83));
+    value += Saturating(0xff);
+    assert_eq!(value.get_bits(4..), Saturating(0xf));
+}
+
+#[test]
+fn test_nonzero_types() {
+    use core::num::{NonZeroI32, NonZeroU64};
+    use NonZeroBitField;
+    use ZeroError;
+
+    let mut handle = NonZeroU64::new(0x8000_0000_0000_0001).unwrap();
+    assert!(handle.get_bit(63));
+    assert_eq!(handle.get_bits(1..63), 0);
+    handle.try_set_bit(63, false).unwrap();
+    assert_eq!(handle.get(), 1);
+    assert_eq!(handle.try_set_bit(0, false), Err(ZeroError));
+    assert_eq!(handle.try_set_bits(.., 0), Err(ZeroError));
+    assert_eq!(handle.get(), 1);
+    handle.try_set_bits(32.., 0xabcd).unwrap();
+    assert_eq!(handle.get(), 0xabcd_0000_0001);
+
+    let mut value = NonZeroI32::new(0x1234_5678).unwrap();
+    value.try_set_bits(16.., 0).unwrap();
+    assert_eq!(value.get(), 0x5678);
+    assert_eq!(value.get_bits(0..16), 0x5678);
+    value.try_set_bit(31, true).unwrap();
+    assert!(value.get() < 0);
+}
+
+#[test]
+fn test_wide_bit_field() {
+    use Wide;
+
+    assert_eq!(Wide::<u64, 4>::BIT_LENGTH, 256);
+    assert_eq!(Wide::<u8, 3>::BIT_LENGTH, 24);
+
+    let mut field = Wide([0u8; 3]);
+    field.set_bit(9, true);
+    assert_eq!(field, Wide([0x00, 0x02, 0x00]));
+    assert!(field.get_bit(9));
+    assert!(!field.get_bit(8));
+
+    field.set_bits(4..20, Wide([0xcd, 0xab, 0]));
+    assert_eq!(field, Wide([0xd0, 0xbc, 0x0a]));
+    assert_eq!(field.get_bits(4..20), Wide([0xcd, 0xab, 0]));
+    assert_eq!(field.get_bits(4..=19), Wide([0xcd, 0xab, 0]));
+    assert_eq!(field.get_bits(..), field);
+    assert_eq!(field.get_bits(12..14), Wide([0b11, 0, 0]));
+    assert_eq!(field.get_bits(5..5), Wide([0, 0, 0]));
+
+    field.set_bits(.., Wide([1, 2, 3]));
+    assert_eq!(field, Wide([1, 2, 3]));
+    field.set_bits(8..16, Wide([0, 0, 0]));
+    assert_eq!(field, Wide([1, 0, 3]));
+
+    let mut wide = Wide([u64::MAX; 4]);
+    wide.set_bits(1..255, Wide::default());
+    assert_eq!(wide, Wide([1, 0, 0, 1 << 63]));
+    assert_eq!(wide.get_bits(63..193), Wide([0, 0, 0, 0]));
+    assert_eq!(wide.get_bits(192..), Wide([1 << 63, 0, 0, 0]));
+
+    let mut signed = Wide([0x8000i32, 0]);
+    assert_eq!(signed.get_bits(0..16), Wide([0x8000, 0]));
+    assert_eq!(signed.get_bits(8..40), Wide([0x80, 0]));
+    signed.set_bits(32..48, Wide([0x8000, 0]));
+    assert_eq!(signed, Wide([0x8000, 0x8000]));
+    signed.set_bits(16..48, Wide([-1, 0]));
+    assert_eq!(signed, Wide([-0x8000, 0xffff]));
+    assert_eq!(signed.get_bits(15..47), Wide([-1, 0]));
+    assert_eq!(signed.get_bits(..), signed);
+}
+
+#[test]
+fn test_zero_length_bit_field() {
+    use Wide;
+
+    let empty = Wide::<u8, 0>::default();
+    assert_eq!(empty.extract_by_mask(empty), empty);
+    assert_eq!(empty.get_split_bits(&[0..0, 0..0]), empty);
+    assert_eq!(empty.permute_bits(&[]), empty);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_wide_set_bits_value_too_large() {
+    use Wide;
+
+    let mut field = Wide([0u8; 3]);
+    field.set_bits(4..14, Wide([0xff, 0x04, 0]));
+}
+
+#[test]
+fn test_get_bits_into() {
+    let array = [
+        0x0123_4567_89ab_cdefu64,
+        0xfedc_ba98_7654_3210,
+        0xaaaa_aaaa_5555_5555,
+        0xffff_0000_ffff_0000,
+    ];
+
+    let mut out = [0u64; 4];
+    array.get_bits_into(.., &mut out);
+    assert_eq!(out, array);
+
+    let mut out = [0xffu64; 4];
+    array.get_bits_into(4..204, &mut out);
+    assert_eq!(
+        out,
+        [
+            0x0012_3456_789a_bcde,
+            0x5fed_cba9_8765_4321,
+            0x0aaa_aaaa_a555_5555,
+            0,
+        ]
+    );
+
+    let mut out = [0u64; 2];
+    array.get_bits_into(60..72, &mut out);
+    assert_eq!(out, [0x100, 0]);
+    array.get_bits_into(200..200, &mut out);
+    assert_eq!(out, [0x100, 0]);
+
+    let bytes = [0x5au8; 9];
+    for start in 0..8 {
+        for end in start..72 {
+            let mut out = [0u8; 9];
+            bytes.get_bits_into(start..end, &mut out);
+            for i in 0..end - start {
+                assert_eq!(out.get_bit(i), bytes.get_bit(start + i));
+            }
+            for i in end - start..out.bit_length() {
+                assert!(!out.get_bit(i));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_set_bits_from() {
+    let mut array = [0u64; 4];
+    array.set_bits_from(
+        4..204,
+        &[
+            0x0012_3456_789a_bcde,
+            0x5fed_cba9_8765_4321,
+            0x0aaa_aaaa_a555_5555,
+            0xff,
+        ],
+    );
+    assert_eq!(
+        array,
+        [
+            0x0123_4567_89ab_cde0,
+            0xfedc_ba98_7654_3210,
+            0xaaaa_aaaa_5555_5555,
+            0x0000_0000_0000_0ff0,
+        ]
+    );
+
+    for start in 0..8 {
+        for end in start..72 {
+            let mut bytes = [0xa5u8; 9];
+            let src = [0x12u8, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x0f];
+            let mut used = [0u8; 9];
+            src.get_bits_into(..end - start, &mut used);
+            bytes.set_bits_from(start..end, &used);
+            for i in 0..72 {
+                let expected = if i >= start && i < end {
+                    src.get_bit(i - start)
+                } else {
+                    [0xa5u8].get_bit(i % 8)
+                };
+                assert_eq!(bytes.get_bit(i), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bits_into_from_signed() {
+    let mut out = [-1i32; 2];
+    [0x8000i32, 0].get_bits_into(0..16, &mut out);
+    assert_eq!(out, [0x8000, -1]);
+    [-1i32, i32::MIN].get_bits_into(16..64, &mut out);
+    assert_eq!(out, [0xffff, 0x8000]);
+
+    let mut array = [0i32; 2];
+    array.set_bits_from(8..24, &[0x8000]);
+    assert_eq!(array, [0x0080_0000, 0]);
+    array.set_bits_from(24..56, &[-1]);
+    assert_eq!(array, [-0x0080_0000, 0x00ff_ffff]);
+    array.set_bits_from(.., &[0, i32::MIN]);
+    assert_eq!(array, [0, i32::MIN]);
+
+    let bytes = [-0x5bi8, 0x3c, -0x01, 0x70];
+    for start in 0..16 {
+        for end in start..32 {
+            let mut out = [-1i8; 4];
+            bytes.get_bits_into(start..end, &mut out);
+            let mut copy = [0x55i8; 4];
+            copy.set_bits_from(start..end, &out);
+            for i in 0..32 {
+                if i < end - start {
+                    assert_eq!(out.get_bit(i), bytes.get_bit(start + i));
+                }
+                let expected = if (start..end).contains(&i) {
+                    bytes.get_bit(i)
+                } else {
+                    [0x55i8].get_bit(i % 8)
+                };
+                assert_eq!(copy.get_bit(i), expected);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_get_bits_into_out_too_short() {
+    let mut out = [0u8; 2];
+    [0u8; 4].get_bits_into(3..20, &mut out);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_set_bits_from_value_too_large() {
+    [0u8; 4].set_bits_from(3..15, &[0xff, 0x10]);
+}
+
+#[test]
+fn test_copy_bits() {
+    use copy_bits;
+
+    let src = [
+        0x0123_4567_89ab_cdefu64,
+        0xfedc_ba98_7654_3210,
+        0x5555_aaaa_5555_aaaa,
+    ];
+    let mut dst = [0u64; 4];
+    copy_bits(&src, .., &mut dst, 0);
+    assert_eq!(dst[..3], src);
+
+    let mut dst = [u64::MAX; 4];
+    copy_bits(&src, 60..190, &mut dst, 71);
+    for i in 0..256 {
+        let expected = if (71..201).contains(&i) {
+            src.get_bit(i - 71 + 60)
+        } else {
+            true
+        };
+        assert_eq!(dst.get_bit(i), expected);
+    }
+
+    let src = [0x5a3cu16, 0x0ff0, 0x1234];
+    for start in 0..20 {
+        for end in start..48 {
+            for dst_start in 0..=64 - (end - start) {
+                let mut dst = [0u16; 4];
+                copy_bits(&src, start..end, &mut dst, dst_start);
+                for i in 0..64 {
+                    let expected = i >= dst_start
+                        && i < dst_start + end - start
+                        && src.get_bit(i - dst_start + start);
+                    assert_eq!(dst.get_bit(i), expected);
+                }
+            }
+        }
+    }
+
+    let mut dst = [0i32; 2];
+    copy_bits(&[0x8000i32], 0..16, &mut dst, 0);
+    assert_eq!(dst, [0x8000, 0]);
+    copy_bits(&[-1i32], 4..32, &mut dst, 20);
+    assert_eq!(dst, [-0x0010_0000 | 0x8000, 0xffff]);
+
+    let src = [-0x5bi8, 0x3c, -0x01];
+    for start in 0..12 {
+        for end in start..24 {
+            for dst_start in 0..=32 - (end - start) {
+                let mut dst = [0x55i8; 4];
+                copy_bits(&src, start..end, &mut dst, dst_start);
+                for i in 0..32 {
+                    let expected = if i >= dst_start && i < dst_start + end - start {
+                        src.get_bit(i - dst_start + start)
+                    } else {
+                        [0x55i8].get_bit(i % 8)
+                    };
+                    assert_eq!(dst.get_bit(i), expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_copy_within_bits() {
+    let original = [0x5a3cu16, 0x0ff0, 0x1234, 0xfedc];
+    for start in 0..20 {
+        for end in start..64 {
+            for dest in 0..=64 - (end - start) {
+                let mut array = original;
+                array.copy_within_bits(start..end, dest);
+                for i in 0..64 {
+                    let expected = if i >= dest && i < dest + end - start {
+                        original.get_bit(i - dest + start)
+                    } else {
+                        original.get_bit(i)
+                    };
+                    assert_eq!(array.get_bit(i), expected);
+                }
+            }
+        }
+    }
+
+    let mut array = [8i8, 0];
+    array.copy_within_bits(0..4, 8);
+    assert_eq!(array, [8, 8]);
+    array.copy_within_bits(0..4, 4);
+    assert_eq!(array, [-0x78, 8]);
+
+    let original = [-0x5bi8, 0x3c, -0x01, 0x70];
+    for start in 0..12 {
+        for end in start..32 {
+            for dest in 0..=32 - (end - start) {
+                let mut array = original;
+                array.copy_within_bits(start..end, dest);
+                for i in 0..32 {
+                    let expected = if i >= dest && i < dest + end - start {
+                        original.get_bit(i - dest + start)
+                    } else {
+                        original.get_bit(i)
+                    };
+                    assert_eq!(array.get_bit(i), expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_copy_within_bits_out_of_bounds() {
+    [0u8; 2].copy_within_bits(4..12, 9);
+}
+
+#[test]
+fn test_shift_bits_array() {
+    let original = [
+        0x0123_4567_89ab_cdefu64,
+        0xfedc_ba98_7654_3210,
+        0x5555_aaaa_0f0f_f0f0,
+    ];
+    for n in 0..200 {
+        let mut left = original;
+        left.shl_bits(n);
+        let mut right = original;
+        right.shr_bits(n);
+        for i in 0..192 {
+            assert_eq!(left.get_bit(i), i >= n && original.get_bit(i - n));
+            assert_eq!(right.get_bit(i), i + n < 192 && original.get_bit(i + n));
+        }
+    }
+
+    let mut value = [0x8000_0000u32, 0x1];
+    value.shl_bits(1);
+    assert_eq!(value, [0, 0x3]);
+    value.shr_bits(33);
+    assert_eq!(value, [1, 0]);
+
+    let mut value = [8i8, 0];
+    value.shl_bits(4);
+    assert_eq!(value, [-128, 0]);
+    let mut value = [-1i8, 0, 0];
+    value.shl_bits(4);
+    assert_eq!(value, [-16, 15, 0]);
+    value.shr_bits(8);
+    assert_eq!(value, [15, 0, 0]);
+
+    let original = [-0x5bi8, 0x3c, -0x01];
+    for n in 0..30 {
+        let mut left = original;
+        left.shl_bits(n);
+        let mut right = original;
+        right.shr_bits(n);
+        for i in 0..24 {
+            assert_eq!(left.get_bit(i), i >= n && original.get_bit(i - n));
+            assert_eq!(right.get_bit(i), i + n < 24 && original.get_bit(i + n));
+        }
+    }
+}
+
+#[test]
+fn test_rotate_bits_array() {
+    let original = [0x5a3cu16, 0x0ff0, 0x1234];
+    for n in 0..100 {
+        let mut left = original;
+        left.rotate_left_bits(n);
+        let mut right = original;
+        right.rotate_right_bits(n);
+        for i in 0..48 {
+            assert_eq!(left.get_bit((i + n) % 48), original.get_bit(i));
+            assert_eq!(right.get_bit(i), original.get_bit((i + n) % 48));
+        }
+    }
+
+    let mut value = [0x8000_0000_0000_0001u64, 0x8000_0000_0000_0000];
+    value.rotate_left_bits(1);
+    assert_eq!(value, [0x3, 0x1]);
+    value.rotate_right_bits(2);
+    assert_eq!(value, [0x4000_0000_0000_0000, 0xc000_0000_0000_0000]);
+
+    let mut empty: [u8; 0] = [];
+    empty.rotate_left_bits(3);
+    empty.shl_bits(3);
+
+    let mut value = [-0x7fff_ffffi32, 0];
+    value.rotate_left_bits(4);
+    assert_eq!(value, [0x10, 0x8]);
+    value.rotate_right_bits(8);
+    assert_eq!(value, [0x0800_0000, 0x1000_0000]);
+
+    let original = [-0x5bi8, 0x3c, -0x01];
+    for n in 0..50 {
+        let mut left = original;
+        left.rotate_left_bits(n);
+        let mut right = original;
+        right.rotate_right_bits(n);
+        for i in 0..24 {
+            assert_eq!(left.get_bit((i + n) % 24), original.get_bit(i));
+            assert_eq!(right.get_bit(i), original.get_bit((i + n) % 24));
+        }
+    }
+}
+
+#[test]
+fn test_shift_range_array() {
+    let original = [0x5a3cu16, 0x0ff0, 0x1234];
+    for start in 0..20 {
+        for end in start..48 {
+            for n in -50isize..50 {
+                let mut array = original;
+                array.shift_range(start..end, n);
+                for i in 0..48 {
+                    let expected = if i < start || i >= end {
+                        original.get_bit(i)
+                    } else {
+                        let source = i as isize - n;
+                        source >= start as isize
+                            && source < end as isize
+                            && original.get_bit(source as usize)
+                    };
+                    assert_eq!(array.get_bit(i), expected);
+                }
+            }
+        }
+    }
+
+    let original = [-0x5bi8, 0x3c, -0x01];
+    for start in 0..12 {
+        for end in start..24 {
+            for n in -26isize..26 {
+                let mut array = original;
+                array.shift_range(start..end, n);
+                for i in 0..24 {
+                    let expected = if i < start || i >= end {
+                        original.get_bit(i)
+                    } else {
+                        let source = i as isize - n;
+                        source >= start as isize
+                            && source < end as isize
+                            && original.get_bit(source as usize)
+                    };
+                    assert_eq!(array.get_bit(i), expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_get_bits_as() {
+    let bytes = [0x21u8, 0x43, 0x65, 0x87, 0xa9, 0xcb, 0xed, 0x0f, 0x11];
+    assert_eq!(bytes.get_bits_as::<u64>(..64), 0x0fed_cba9_8765_4321);
+    assert_eq!(bytes.get_bits_as::<u64>(4..68), 0x10fe_dcba_9876_5432);
+    assert_eq!(bytes.get_bits_as::<u32>(3..3), 0);
+    assert_eq!(bytes.get_bits_as::<u128>(..), 0x11_0fed_cba9_8765_4321);
+    assert_eq!(bytes.get_bits_as::<u8>(4..12), 0x32);
+
+    let words = [0x0123_4567_89ab_cdefu64, 0xfedc_ba98_7654_3210];
+    assert_eq!(words.get_bits_as::<u8>(60..68), 0x00);
+    assert_eq!(words.get_bits_as::<u16>(56..72), 0x1001);
+    assert_eq!(words.get_bits_as::<u32>(..32), 0x89ab_cdef);
+    assert_eq!(
+        words.get_bits_as::<u128>(..),
+        0xfedc_ba98_7654_3210_0123_4567_89ab_cdef
+    );
+
+    for start in 0..16 {
+        for end in start..start + 32 {
+            let value = bytes.get_bits_as::<u32>(start..end);
+            for i in 0..32 {
+                assert_eq!(
+                    value.get_bit(i),
+                    i < end - start && bytes.get_bit(start + i)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_set_bits_as() {
+    let mut bytes = [0xffu8; 9];
+    bytes.set_bits_as(4..68, 0x0123_4567_89ab_cdefu64);
+    assert_eq!(
+        bytes,
+        [0xff, 0xde, 0xbc, 0x9a, 0x78, 0x56, 0x34, 0x12, 0xf0]
+    );
+    bytes.set_bits_as(8..20, 0x0u16);
+    assert_eq!(bytes[..3], [0xff, 0x00, 0xb0]);
+
+    let mut words = [0u64; 2];
+    words.set_bits_as(60..68, 0xa5u8);
+    assert_eq!(words, [0x5000_0000_0000_0000, 0xa]);
+    words.set_bits_as(.., u128::MAX);
+    assert_eq!(words, [u64::MAX; 2]);
+
+    for start in 0..16 {
+        for end in start..start + 32 {
+            let mut bytes = [0u8; 6];
+            let value = 0xdead_beefu32.get_bits(..end - start);
+            bytes.set_bits_as(start..end, value);
+            assert_eq!(bytes.get_bits_as::<u32>(start..end), value);
+            assert_eq!(bytes.get_bits_as::<u64>(..48), u64::from(value) << start);
+        }
+    }
+}
+
+#[test]
+fn test_bits_as_signed() {
+    // bit patterns are moved as they are, without sign extension or range checks
+    assert_eq!([-1i8, 0].get_bits_as::<u32>(0..16), 0x00ff);
+    assert_eq!([0xffffu32].get_bits_as::<i16>(0..16), -1);
+    assert_eq!([0x80u8, 0x7f].get_bits_as::<i16>(..), 0x7f80);
+    assert_eq!([0x0000_8000i32].get_bits_as::<i8>(8..16), i8::MIN);
+    assert_eq!([-1i64; 2].get_bits_as::<i128>(..), -1);
+
+    let mut words = [0u32];
+    words.set_bits_as(0..16, -1i16);
+    assert_eq!(words, [0xffff]);
+
+    let mut bytes = [0i8; 3];
+    bytes.set_bits_as(4..20, 0x8001u16);
+    assert_eq!(bytes, [0x10, 0x00, 0x08]);
+    bytes.set_bits_as(8..16, -1i8);
+    assert_eq!(bytes, [0x10, -1, 0x08]);
+    bytes.set_bits_as(.., -2i32 & 0x00ff_ffff);
+    assert_eq!(bytes, [-2, -1, -1]);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_set_bits_as_value_too_large() {
+    [0u8; 4].set_bits_as(3..15, 0x1000u16);
+}
+
+#[test]
+fn test_unchecked_accessors() {
+    let mut field = 0b1111111111010110u32;
+    unsafe {
+        assert!(field.get_bit_unchecked(1));
+        assert!(!field.get_bit_unchecked(3));
+        assert_eq!(field.get_bits_unchecked(6..16), 0b1111111111);
+        assert_eq!(field.get_bits_unchecked(..), field);
+        assert_eq!(field.get_bits_unchecked(4..4), 0);
+
+        field.set_bit_unchecked(31, true);
+        assert_eq!(field, 0x8000_ffd6);
+        field.set_bits_unchecked(8..16, 0x12);
+        assert_eq!(field, 0x8000_12d6);
+        field.set_bits_unchecked(3..3, 0);
+        assert_eq!(field, 0x8000_12d6);
+    }
+
+    let mut array = [0xffu8, 0x00u8, 0xffu8];
+    unsafe {
+        assert!(array.get_bit_unchecked(7));
+        assert!(!array.get_bit_unchecked(8));
+        assert_eq!(array.get_bits_unchecked(7..9), 0b01);
+        assert_eq!(array.get_bits_unchecked(12..20), 0xf0);
+        assert_eq!(array.get_bits_unchecked(16..24), 0xff);
+
+        array.set_bit_unchecked(8, true);
+        assert_eq!(array, [0xff, 0x01, 0xff]);
+        array.set_bits_unchecked(12..20, 0xaa);
+        assert_eq!(array, [0xff, 0xa1, 0xfa]);
+        array.set_bits_unchecked(16..24, 0x00);
+        assert_eq!(array, [0xff, 0xa1, 0x00]);
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn test_unchecked_out_of_bounds_debug() {
+    let value = 0u16;
+    unsafe {
+        value.get_bits_unchecked(8..17);
+    }
+}
+
+#[test]
+fn test_const_ranges() {
+    let mut field = 0b1111111111010110u32;
+    assert_eq!(field.get_bits_const::<0, 6>(), 0b010110);
+    assert_eq!(field.get_bits_const::<6, 16>(), 0b1111111111);
+    assert_eq!(field.get_bits_const::<16, 32>(), 0);
+    assert_eq!(field.get_bits_const::<0, 32>(), field);
+    assert_eq!(field.get_bits_const::<5, 5>(), 0);
+
+    field.set_bits_const::<10, 15>(0b10101);
+    assert_eq!(field.get_bits(10..15), 0b10101);
+    field.set_bits_const::<0, 32>(0x1234_5678);
+    assert_eq!(field, 0x1234_5678);
+
+    let mut wide = 0u128;
+    wide.set_bits_const::<64, 128>(u64::MAX as u128);
+    assert_eq!(wide.get_bits_const::<60, 68>(), 0xf0);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_set_bits_const_value_too_large() {
+    let mut field = 0u8;
+    field.set_bits_const::<0, 3>(0b1000);
+}
+
+#[test]
+fn test_bit_range() {
+    use BitRange;
+
+    let range = BitRange::new(4, 12);
+    assert_eq!(range.start(), 4);
+    assert_eq!(range.end(), 12);
+    assert_eq!(range.len(), 8);
+    assert!(!range.is_empty());
+    assert!(BitRange::new(3, 3).is_empty());
+    assert!(range.contains(11));
+    assert!(!range.contains(3));
+
+    assert_eq!(range.mask::<u32>(), 0xff0);
+    assert_eq!(BitRange::new(4, 8).mask::<u8>(), 0xf0);
+    assert_eq!(BitRange::new(64, 128).mask::<u128>(), u128::MAX << 64);
+    assert_eq!(BitRange::new(0, 4).mask::<i8>(), 0x0f);
+    assert_eq!(BitRange::new(4, 8).mask::<i8>(), -0x10);
+    assert_eq!(BitRange::new(8, 24).mask::<i32>(), 0x00ff_ff00);
+    assert_eq!(BitRange::new(0, 32).mask::<i32>(), -1);
+    assert_eq!(BitRange::new(31, 32).mask::<i32>(), i32::MIN);
+
+    let value = 0xabcdu16;
+    assert_eq!(value.get_bits(range), 0xbc);
+    let mut field = 0u16;
+    field.set_bits(range, 0x5a);
+    assert_eq!(field, 0x05a0);
+    let array = [0xf0u8, 0x0f];
+    assert_eq!(array.get_bits(range), 0xff);
+
+    assert_eq!(
+        range.intersect(BitRange::new(0, 6)),
+        Some(BitRange::new(4, 6))
+    );
+    assert_eq!(
+        range.intersect(BitRange::new(6, 8)),
+        Some(BitRange::new(6, 8))
+    );
+    assert_eq!(range.intersect(BitRange::new(0, 4)), None);
+    assert_eq!(range.intersect(BitRange::new(8, 8)), None);
+
+    assert_eq!(
+        range.union_if_contiguous(BitRange::new(0, 4)),
+        Some(BitRange::new(0, 12))
+    );
+    assert_eq!(range.union_if_contiguous(BitRange::new(6, 8)), Some(range));
+    assert_eq!(
+        range.union_if_contiguous(BitRange::new(20, 20)),
+        Some(range)
+    );
+    assert_eq!(range.union_if_contiguous(BitRange::new(13, 20)), None);
+
+    assert_eq!(range.shift(-4), BitRange::new(0, 8));
+    assert_eq!(range.shift(0), range);
+    let (low, high) = range.split_at(4);
+    assert!(low.is_empty());
+    assert_eq!(high, range);
+    assert_eq!(range.split_at(12), (range, BitRange::new(12, 12)));
+
+    assert_eq!(range.iter().sum::<usize>(), 60);
+    assert_eq!(range.into_iter().count(), 8);
+    assert_eq!(BitRange::from(2..5), BitRange::new(2, 5));
+    assert_eq!(core::ops::Range::from(range), 4..12);
+
+    assert_eq!(BitRange::checked::<u32>(0, 32), Some(BitRange::new(0, 32)));
+    assert_eq!(BitRange::checked::<u32>(32, 32), None);
+    assert_eq!(
+        BitRange::from_bounds::<u64, _>(..),
+        Some(BitRange::new(0, 64))
+    );
+    assert_eq!(
+        BitRange::from_bounds::<u64, _>(8..=15),
+        Some(BitRange::new(8, 16))
+    );
+    assert_eq!(BitRange::from_bounds::<u8, _>(4..12), None);
+}
+
+#[test]
+#[should_panic(expected = "shifted bit range is out of bounds")]
+fn test_bit_range_shift_underflow() {
+    use BitRange;
+
+    BitRange::new(2, 6).shift(-3);
+}
+
+/// A small xorshift generator, so the mask tests cover many inputs without dependencies.
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn naive_extract(value: u64, mask: u64) -> u64 {
+    let mut result = 0;
+    let mut index = 0;
+    for bit in 0..64 {
+        if mask >> bit & 1 == 1 {
+            result |= (value >> bit & 1) << index;
+            index += 1;
+        }
+    }
+    result
+}
+
+fn naive_deposit(value: u64, mask: u64) -> u64 {
+    let mut result = 0;
+    let mut index = 0;
+    for bit in 0..64 {
+        if mask >> bit & 1 == 1 {
+            result |= (value >> index & 1) << bit;
+            index += 1;
+        }
+    }
+    result
+}
+
+#[test]
+fn test_extract_deposit_by_mask() {
+    use core::num::Wrapping;
+    use Wide;
+
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    for _ in 0..1000 {
+        let value = xorshift(&mut state);
+        let mask = xorshift(&mut state) & xorshift(&mut state);
+        let packed = naive_extract(value, mask);
+
+        assert_eq!(value.extract_by_mask(mask), packed);
+        assert_eq!(
+            (value as u32).extract_by_mask(mask as u32) as u64,
+            naive_extract(value as u32 as u64, mask as u32 as u64)
+        );
+        assert_eq!(
+            (value as i16).extract_by_mask(mask as i16) as u16 as u64,
+            naive_extract(value as u16 as u64, mask as u16 as u64)
+        );
+
+        let mut target = value;
+        target.deposit_by_mask(mask, packed);
+        assert_eq!(target, value);
+
+        let mut target = !mask;
+        target.deposit_by_mask(mask, packed);
+        assert_eq!(target, !mask | naive_deposit(packed, mask));
+
+        // the generic implementation must agree with the specialized one
+        let mut wrapped = Wrapping(!mask);
+        wrapped.deposit_by_mask(Wrapping(mask), Wrapping(packed));
+        assert_eq!(wrapped.0, target);
+        assert_eq!(Wrapping(value).extract_by_mask(Wrapping(mask)).0, packed);
+        assert_eq!(Wide([value]).extract_by_mask(Wide([mask])), Wide([packed]));
+    }
+
+    assert_eq!(u8::MAX.extract_by_mask(0), 0);
+    assert_eq!(0x1234u16.extract_by_mask(u16::MAX), 0x1234);
+    assert_eq!(*(-1i32).deposit_by_mask(i32::MIN, 0), i32::MAX);
+    assert_eq!(*0i32.deposit_by_mask(i32::MIN, 1), i32::MIN);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into mask")]
+fn test_deposit_by_mask_overflow() {
+    0u32.deposit_by_mask(0b1010_1010, 0b1_0000);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into mask")]
+fn test_deposit_by_mask_overflow_generic() {
+    use core::num::Wrapping;
+
+    Wrapping(0u32).deposit_by_mask(Wrapping(0b1010_1010), Wrapping(0b1_0000));
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn test_extract_deposit_by_mask_bmi2() {
+    use core::arch::x86_64::{_pdep_u32, _pdep_u64, _pext_u32, _pext_u64};
+
+    #[target_feature(enable = "bmi2")]
+    unsafe fn check(value: u64, mask: u64) {
+        assert_eq!(value.extract_by_mask(mask), _pext_u64(value, mask));
+        assert_eq!(
+            *0u64.deposit_by_mask(mask, _pext_u64(value, mask)),
+            _pdep_u64(_pext_u64(value, mask), mask)
+        );
+
+        let (value, mask) = (value as u32, mask as u32);
+        assert_eq!(value.extract_by_mask(mask), _pext_u32(value, mask));
+        assert_eq!(
+            *0u32.deposit_by_mask(mask, _pext_u32(value, mask)),
+            _pdep_u32(_pext_u32(value, mask), mask)
+        );
+    }
+
+    if !std::is_x86_feature_detected!("bmi2") {
+        return;
+    }
+
+    let mut state = 0x9e37_79b9_7f4a_7c15;
+    for _ in 0..1000 {
+        let value = xorshift(&mut state);
+        let mask = xorshift(&mut state) & xorshift(&mut state);
+        unsafe { check(value, mask) };
+    }
+}
+
+#[test]
+fn test_split_bits() {
+    // the limit and base fields of an x86 segment descriptor
+    let limit = [0..16, 48..52];
+    let base = [16..40, 56..64];
+
+    let mut descriptor = 0u64;
+    descriptor.set_split_bits(&limit, 0xa_bcde);
+    descriptor.set_split_bits(&base, 0xfedc_ba98);
+    assert_eq!(descriptor, 0xfe0a_00dc_ba98_bcde);
+    assert_eq!(descriptor.get_split_bits(&limit), 0xa_bcde);
+    assert_eq!(descriptor.get_split_bits(&base), 0xfedc_ba98);
+
+    // ranges are concatenated in the given order, not sorted
+    assert_eq!(0x00f0u16.get_split_bits(&[4..6, 0..4, 6..8]), 0b1100_0011);
+    assert_eq!(0x1234u16.get_split_bits(&[]), 0);
+    assert_eq!(0x1234u16.get_split_bits(&[3..3, 0..16]), 0x1234);
+    assert_eq!(*0u16.set_split_bits(&[8..8, 0..16], 0xffff), 0xffff);
+    assert_eq!(i8::MIN.get_split_bits(&[7..8, 0..7]), 1);
+    assert_eq!(*0i8.set_split_bits(&[7..8, 0..0], 1), i8::MIN);
+
+    let mut array = [0u32; 2];
+    array.set_split_bits(&[28..36, 60..64], 0xabc);
+    assert_eq!(array, [0xc000_0000, 0xa000_000b]);
+    assert_eq!(array.get_split_bits(&[28..36, 60..64]), 0xabc);
+    assert_eq!(array.get_split_bits(&[60..64, 28..36]), 0xbca);
+
+    let mut empty: [u32; 0] = [];
+    assert_eq!(empty.get_split_bits(&[]), 0);
+    assert_eq!(empty.get_split_bits(&[0..0, 0..0]), 0);
+    empty.set_split_bits(&[], 0);
+}
+
+#[test]
+#[should_panic(expected = "bit ranges overlap")]
+fn test_split_bits_overlap() {
+    0u32.get_split_bits(&[0..8, 16..20, 7..9]);
+}
+
+#[test]
+#[should_panic(expected = "bit ranges exceed the bit length of the value")]
+fn test_split_bits_too_long() {
+    [0u8; 4].get_split_bits(&[0..4, 8..13]);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit ranges")]
+fn test_split_bits_value_too_large() {
+    0u32.set_split_bits(&[0..4, 8..12], 0x100);
+}
+
+#[test]
+fn test_set_fields() {
+    use core::num::Wrapping;
+
+    let fields = [
+        (0..4, 0x3),
+        (4..5, 1),
+        (16..32, 0xbeef),
+        (40..40, 0),
+        (60..64, 0xf),
+    ];
+    let mut value = 0x0123_4567_89ab_cdefu64;
+    value.set_fields(&fields);
+    assert_eq!(value, 0xf123_4567_beef_cdf3);
+    assert_eq!(0x0123_4567_89ab_cdefu64.with_fields(&fields), value);
+    assert_eq!(0u8.with_fields(&[]), 0);
+    assert_eq!(0u8.with_fields(&[(0..8, 0xa5)]), 0xa5);
+
+    // the generic implementation must agree with the specialized one
+    let wrapped: [_; 5] = fields
+        .clone()
+        .map(|(range, value)| (range, Wrapping(value)));
+    assert_eq!(
+        Wrapping(0x0123_4567_89ab_cdefu64).with_fields(&wrapped),
+        Wrapping(value)
+    );
+
+    assert_eq!(0i32.with_fields(&[(31..32, 1), (0..1, 1)]), i32::MIN + 1);
+    assert_eq!((-1i16).with_fields(&[(4..12, 0)]), !0x0ff0);
+    assert_eq!(0i64.with_fields(&[(0..64, -1)]), -1);
+}
+
+#[test]
+fn test_set_fields_unchanged_on_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut value = 0x1234u16;
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        value.set_fields(&[(0..4, 0xf), (8..12, 0x10)]);
+    }));
+    assert!(result.is_err());
+    assert_eq!(value, 0x1234);
+}
+
+#[test]
+#[should_panic(expected = "bit ranges overlap")]
+fn test_set_fields_overlap() {
+    0u32.set_fields(&[(0..8, 0), (4..12, 0)]);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_set_fields_value_too_large() {
+    0u32.set_fields(&[(0..8, 0), (8..12, 0x10)]);
+}
+
+#[test]
+fn test_bits_masked() {
+    use core::num::Wrapping;
+
+    let value = 0x0123_4567_89ab_cdefu64;
+    assert_eq!(value.get_bits_masked(8..24), 0x0000_0000_00ab_cd00);
+    assert_eq!(value.get_bits_masked(60..), 0);
+    assert_eq!(value.get_bits_masked(..), value);
+    assert_eq!(value.get_bits_masked(4..4), 0);
+    assert_eq!(Wrapping(value).get_bits_masked(8..24), Wrapping(0xab_cd00));
+    assert_eq!((-1i8).get_bits_masked(7..), i8::MIN);
+    assert_eq!((-1i8).get_bits_masked(2..4), 0b1100);
+
+    let mut value = value;
+    value.set_bits_masked(8..24, 0x0000_0000_0012_3400);
+    assert_eq!(value, 0x0123_4567_8912_34ef);
+    value.set_bits_masked(.., 0);
+    assert_eq!(value, 0);
+    let mut wrapped = Wrapping(0xffffu16);
+    wrapped.set_bits_masked(4..8, Wrapping(0x50));
+    assert_eq!(wrapped, Wrapping(0xff5f));
+    let mut signed = -1i16;
+    signed.set_bits_masked(4..8, 0x50);
+    assert_eq!(signed, !0xa0);
+    signed.set_bits_masked(15..16, 0);
+    assert_eq!(signed, i16::MAX & !0xa0);
+
+    let mut array = [0u8, 0xff, 0x0f];
+    assert_eq!(array.get_bits_masked(12..16), 0xf0);
+    assert_eq!(array.get_bits_masked(16..24), 0x0f);
+    array.set_bits_masked(10..14, 0b0010_0000);
+    assert_eq!(array, [0, 0xe3, 0x0f]);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_set_bits_masked_outside_range() {
+    0u32.set_bits_masked(4..8, 0x0f);
+}
+
+#[test]
+#[should_panic(expected = "bit range spans multiple elements")]
+fn test_bits_masked_spanning_elements() {
+    [0u8; 2].get_bits_masked(4..12);
+}
+
+#[test]
+fn test_set_range_to() {
+    let mut state = 0x1234_5678_9abc_def0;
+    for _ in 0..500 {
+        let mut value = [0u8; 6];
+        for byte in &mut value {
+            *byte = xorshift(&mut state) as u8;
+        }
+        let a = xorshift(&mut state) as usize % 49;
+        let b = xorshift(&mut state) as usize % 49;
+        let (start, end) = (a.min(b), a.max(b));
+        let fill = xorshift(&mut state) & 1 == 1;
+
+        let mut expected = value;
+        for bit in start..end {
+            expected.set_bit(bit, fill);
+        }
+        value.set_range_to(start..end, fill);
+        assert_eq!(value, expected);
+    }
+
+    let mut value = [0i32; 3];
+    value.fill_range(..);
+    assert_eq!(value, [-1; 3]);
+    value.clear_range(31..65);
+    assert_eq!(value, [i32::MAX, 0, -2]);
+    value.clear_range(96..);
+    value.fill_range(0..0);
+    assert_eq!(value, [i32::MAX, 0, -2]);
+}
+
+#[test]
+#[should_panic]
+fn test_set_range_to_out_of_bounds() {
+    [0u16; 2].fill_range(8..33);
+}
+
+#[test]
+fn test_field_arithmetic_exhaustive() {
+    for start in 0..8 {
+        for end in start..=8 {
+            let len = end - start;
+            let modulus = 1u32 << len;
+            for value in 0..=u8::MAX {
+                let field = (value as u32 >> start) % modulus;
+                for n in 0..modulus {
+                    let n8 = n as u8;
+                    let replace = |field: u32| {
+                        let mut expected = value;
+                        for bit in start..end {
+                            expected.set_bit(bit, field >> (bit - start) & 1 == 1);
+                        }
+                        expected
+                    };
+
+                    let mut sum = value;
+                    let overflow = sum.overflowing_add_to_bits(start..end, n8);
+                    assert_eq!(overflow, field + n >= modulus);
+                    assert_eq!(sum, replace((field + n) % modulus));
+
+                    let mut signed_sum = value as i8;
+                    assert_eq!(
+                        signed_sum.overflowing_add_to_bits(start..end, n8 as i8),
+                        overflow
+                    );
+                    assert_eq!(signed_sum as u8, sum);
+
+                    let mut difference = value;
+                    let overflow = difference.overflowing_sub_from_bits(start..end, n8);
+                    assert_eq!(overflow, field < n);
+                    assert_eq!(difference, replace((field + modulus - n) % modulus));
+
+                    let mut signed_difference = value as i8;
+                    assert_eq!(
+                        signed_difference.overflowing_sub_from_bits(start..end, n8 as i8),
+                        overflow
+                    );
+                    assert_eq!(signed_difference as u8, difference);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_field_arithmetic() {
+    use core::num::Wrapping;
+    use Wide;
+
+    // the generic implementation must agree with the specialized one
+    let mut state = 0x0f1e_2d3c_4b5a_6978;
+    for _ in 0..1000 {
+        let value = xorshift(&mut state);
+        let start = xorshift(&mut state) as usize % 64;
+        let end = start + xorshift(&mut state) as usize % (65 - start);
+        let len = end - start;
+        let n = if len == 0 {
+            0
+        } else {
+            xorshift(&mut state) >> (64 - len)
+        };
+
+        let (mut a, mut b) = (value, Wide([value]));
+        assert_eq!(
+            a.overflowing_add_to_bits(start..end, n),
+            b.overflowing_add_to_bits(start..end, Wide([n]))
+        );
+        assert_eq!(Wide([a]), b);
+        assert_eq!(
+            a.overflowing_sub_from_bits(start..end, n),
+            b.overflowing_sub_from_bits(start..end, Wide([n]))
+        );
+        assert_eq!(Wide([a]), b);
+    }
+
+    let mut sequence = Wrapping(0xfffau16);
+    sequence.wrapping_add_to_bits(4.., Wrapping(1));
+    assert_eq!(sequence, Wrapping(0x000a));
+    sequence.wrapping_sub_from_bits(4.., Wrapping(2));
+    assert_eq!(sequence, Wrapping(0xffea));
+
+    let mut retries = 0b1010_0101u8;
+    assert!(retries.checked_add_to_bits(4..7, 0b110).is_none());
+    assert_eq!(retries, 0b1010_0101);
+    retries.saturating_add_to_bits(4..7, 0b110);
+    assert_eq!(retries, 0b1111_0101);
+    retries.saturating_sub_from_bits(0..3, 0b110);
+    assert_eq!(retries, 0b1111_0000);
+    retries.add_to_bits(0..4, 0b1111).sub_from_bits(4..8, 0b1);
+    assert_eq!(retries, 0b1110_1111);
+
+    let mut value = i32::MAX;
+    value.add_to_bits(.., 1);
+    assert_eq!(value, i32::MIN);
+    assert!((-1i64).checked_add_to_bits(.., 1).is_none());
+    assert_eq!(*0i16.wrapping_sub_from_bits(15..16, 1), i16::MIN);
+    assert_eq!(*5u8.saturating_add_to_bits(3..3, 0), 5);
+}
+
+#[test]
+#[should_panic(expected = "attempt to add to bit range with overflow")]
+fn test_add_to_bits_overflow() {
+    0xf0u8.add_to_bits(4..8, 1);
+}
+
+#[test]
+#[should_panic(expected = "attempt to subtract from bit range with overflow")]
+fn test_sub_from_bits_overflow() {
+    0x0fu8.sub_from_bits(4..8, 1);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_add_to_bits_value_too_large() {
+    0u32.wrapping_add_to_bits(4..8, 0x10);
+}
+
+#[test]
+fn test_reorder_bits_in_range() {
+    use Wide;
+
+    // the generic implementation must agree with the specialized one
+    let mut state = 0x5555_3333_0f0f_00ff;
+    for _ in 0..1000 {
+        let value = xorshift(&mut state);
+        let start = xorshift(&mut state) as usize % 64;
+        let end = start + xorshift(&mut state) as usize % (65 - start);
+        let n = xorshift(&mut state) as usize % 130;
+
+        let mut expected = value;
+        for bit in start..end {
+            expected.set_bit(bit, value.get_bit(start + end - 1 - bit));
+        }
+        assert_eq!(*{ value }.reverse_bits_in(start..end), expected);
+        assert_eq!(*Wide([value]).reverse_bits_in(start..end), Wide([expected]));
+        assert_eq!(*(value as i64).reverse_bits_in(start..end), expected as i64);
+
+        let mut expected = value;
+        for bit in start..end {
+            expected.set_bit(
+                start + (bit - start + n) % (end - start),
+                value.get_bit(bit),
+            );
+        }
+        assert_eq!(*{ value }.rotate_left_in(start..end, n), expected);
+        assert_eq!(
+            *Wide([value]).rotate_left_in(start..end, n),
+            Wide([expected])
+        );
+        assert_eq!(
+            *(value as i64).rotate_left_in(start..end, n),
+            expected as i64
+        );
+        assert_eq!(*{ expected }.rotate_right_in(start..end, n), value);
+        assert_eq!(
+            *(expected as i64).rotate_right_in(start..end, n),
+            value as i64
+        );
+
+        let (start, end) = (start / 8 * 8, end / 8 * 8);
+        if start < 64 && start <= end {
+            let mut expected = value;
+            for bit in start..end {
+                let byte = (bit - start) / 8;
+                expected.set_bit(bit, value.get_bit(end - 8 * (byte + 1) + bit % 8));
+            }
+            assert_eq!(*{ value }.swap_bytes_in(start..end), expected);
+            assert_eq!(*Wide([value]).swap_bytes_in(start..end), Wide([expected]));
+            assert_eq!(*(value as i64).swap_bytes_in(start..end), expected as i64);
+        }
+    }
+
+    assert_eq!(*0x1234u16.swap_bytes_in(..), 0x3412);
+    assert_eq!(*0b1000_0001u8.reverse_bits_in(..), 0b1000_0001);
+    assert_eq!(*(-128i8).rotate_left_in(.., 1), 1);
+    assert_eq!(*1i8.rotate_right_in(.., 1), i8::MIN);
+    assert_eq!(*1i8.rotate_right_in(0..7, 1), 0b0100_0000);
+    assert_eq!(*0b0110u8.swap_bits(0, 1).swap_bits(2, 2), 0b0101);
+
+    // rotations by at least the length of the range, through the generic implementation
+    assert_eq!(
+        *Wide([0x0fu8, 0]).rotate_left_in(0..16, usize::MAX),
+        Wide([0x07, 0x80])
+    );
+    assert_eq!(
+        *Wide([0x0fu8, 0]).rotate_right_in(0..16, usize::MAX),
+        Wide([0x1e, 0x00])
+    );
+    assert_eq!(*Wide([0x0fu8, 0]).rotate_left_in(4..12, 8), Wide([0x0f, 0]));
+    assert_eq!(
+        *Wide([0x0fu8, 0]).rotate_left_in(0..12, 13),
+        Wide([0x1e, 0])
+    );
+    assert_eq!(
+        *Wide([0x0fu8, 0]).rotate_left_in(4..4, usize::MAX),
+        Wide([0x0f, 0])
+    );
+}
+
+#[test]
+#[should_panic(expected = "bit range is not byte aligned")]
+fn test_swap_bytes_in_unaligned() {
+    0u32.swap_bytes_in(4..20);
+}
+
+#[test]
+fn test_set_bits_in_range() {
+    use Wide;
+
+    // the generic implementation must agree with the specialized one
+    let mut state = 0x7777_1111_2222_9999;
+    for _ in 0..1000 {
+        let value = xorshift(&mut state) & xorshift(&mut state) & xorshift(&mut state);
+        let start = xorshift(&mut state) as usize % 64;
+        let end = start + xorshift(&mut state) as usize % (65 - start);
+
+        let highest = (start..end).rev().find(|&bit| value.get_bit(bit));
+        let lowest = (start..end).find(|&bit| value.get_bit(bit));
+        let signed = value as i64;
+        let array = [
+            value as u16,
+            (value >> 16) as u16,
+            (value >> 32) as u16,
+            (value >> 48) as u16,
+        ];
+
+        assert_eq!(value.highest_set_bit_in(start..end), highest);
+        assert_eq!(signed.highest_set_bit_in(start..end), highest);
+        assert_eq!(Wide([value]).highest_set_bit_in(start..end), highest);
+        assert_eq!(array.highest_set_bit_in(start..end), highest);
+        assert_eq!(value.lowest_set_bit_in(start..end), lowest);
+        assert_eq!(signed.lowest_set_bit_in(start..end), lowest);
+        assert_eq!(Wide([value]).lowest_set_bit_in(start..end), lowest);
+        assert_eq!(array.lowest_set_bit_in(start..end), lowest);
+
+        let leading = highest.map_or(end - start, |bit| end - 1 - bit);
+        let trailing = lowest.map_or(end - start, |bit| bit - start);
+        assert_eq!(value.leading_zeros_in(start..end), leading);
+        assert_eq!(signed.leading_zeros_in(start..end), leading);
+        assert_eq!(array.leading_zeros_in(start..end), leading);
+        assert_eq!(value.trailing_zeros_in(start..end), trailing);
+        assert_eq!(signed.trailing_zeros_in(start..end), trailing);
+        assert_eq!(array.trailing_zeros_in(start..end), trailing);
+    }
+
+    assert_eq!(u128::MAX.leading_zeros_in(..), 0);
+    assert_eq!(0u128.trailing_zeros_in(..), 128);
+    assert_eq!((-1i8).highest_set_bit_in(..), Some(7));
+    assert_eq!([0u8; 0].highest_set_bit_in(..), None);
+    assert_eq!([0u8; 0].leading_zeros_in(..), 0);
+    assert_eq!([0xffu8; 2].lowest_set_bit_in(16..), None);
+}
+
+/// Spreads the lower `bits` bits of `value` so that bit `i` ends up at position `stride * i`.
+fn naive_spread(value: u64, bits: usize, stride: usize) -> u64 {
+    (0..bits).fold(0, |code, bit| code | (value >> bit & 1) << (stride * bit))
+}
+
+#[test]
+fn test_morton_2d() {
+    use {deinterleave2, interleave2};
+
+    for x in 0..=u8::MAX {
+        for y in 0..=u8::MAX {
+            let code = interleave2(x, y);
+            assert_eq!(
+                code as u64,
+                naive_spread(x as u64, 8, 2) | naive_spread(y as u64, 8, 2) << 1
+            );
+            assert_eq!(deinterleave2(code), (x, y));
+        }
+    }
+
+    let mut state = 0x0123_4567_89ab_cdef;
+    for x in 0..=u16::MAX {
+        let y = xorshift(&mut state) as u16;
+        let code = interleave2(x, y);
+        assert_eq!(
+            code as u64,
+            naive_spread(x as u64, 16, 2) | naive_spread(y as u64, 16, 2) << 1
+        );
+        assert_eq!(deinterleave2(code), (x, y));
+        assert_eq!(deinterleave2(interleave2(y, x)), (y, x));
+    }
+
+    for _ in 0..10000 {
+        let (x, y) = (xorshift(&mut state) as u32, xorshift(&mut state) as u32);
+        let code = interleave2(x, y);
+        assert_eq!(
+            code,
+            naive_spread(x as u64, 32, 2) | naive_spread(y as u64, 32, 2) << 1
+        );
+        assert_eq!(deinterleave2(code), (x, y));
+    }
+    assert_eq!(interleave2(u32::MAX, u32::MAX), u64::MAX);
+    assert_eq!(deinterleave2::<u32>(u64::MAX), (u32::MAX, u32::MAX));
+}
+
+#[test]
+fn test_morton_3d() {
+    use {deinterleave3, interleave3};
+
+    for x in 0..=u8::MAX {
+        for y in 0..=u8::MAX {
+            let z = x ^ y.rotate_left(3);
+            let code = interleave3(x, y, z);
+            assert_eq!(
+                code as u64,
+                naive_spread(x as u64, 8, 3)
+                    | naive_spread(y as u64, 8, 3) << 1
+                    | naive_spread(z as u64, 8, 3) << 2
+            );
+            assert_eq!(deinterleave3(code), (x, y, z));
+            // bits above the interleaved ones are ignored
+            assert_eq!(deinterleave3(code | 0xff00_0000), (x, y, z));
+        }
+    }
+
+    // every 24-bit code corresponds to exactly one triple of bytes
+    for code in 0..1u32 << 24 {
+        let (x, y, z): (u8, u8, u8) = deinterleave3(code);
+        assert_eq!(interleave3(x, y, z), code);
+    }
+
+    let mut state = 0xfedc_ba98_7654_3210;
+    for x in 0..=u16::MAX {
+        let (y, z) = (xorshift(&mut state) as u16, xorshift(&mut state) as u16);
+        let code = interleave3(x, y, z);
+        assert_eq!(
+            code,
+            naive_spread(x as u64, 16, 3)
+                | naive_spread(y as u64, 16, 3) << 1
+                | naive_spread(z as u64, 16, 3) << 2
+        );
+        assert_eq!(deinterleave3(code), (x, y, z));
+    }
+    assert_eq!(interleave3(u16::MAX, u16::MAX, u16::MAX), (1 << 48) - 1);
+}
+
+#[test]
+fn test_gray_code() {
+    use std::vec::Vec;
+    use {GrayCode, GrayCodes};
+
+    for value in 0..=u16::MAX {
+        let gray = value.to_gray();
+        assert_eq!(gray, value ^ (value >> 1));
+        assert_eq!(gray.from_gray(), value);
+        assert_eq!((value as i16).to_gray(), gray as i16);
+        assert_eq!((gray as i16).from_gray(), value as i16);
+        assert_eq!((value as u64).to_gray().from_gray(), value as u64);
+        if value != u16::MAX {
+            assert_eq!((gray ^ (value + 1).to_gray()).count_ones(), 1);
+        }
+    }
+    assert_eq!(i128::MIN.to_gray(), i128::MIN | i128::MIN >> 1 & i128::MAX);
+    assert_eq!(i128::MIN.to_gray().from_gray(), i128::MIN);
+    assert_eq!((-1isize).from_gray().to_gray(), -1);
+
+    let mut value = 0x5a5a_5a5au32;
+    value.to_gray_in(4..12).to_gray_in(28..);
+    assert_eq!(value, 0x7a5a_5f7a);
+    value.from_gray_in(28..).from_gray_in(4..12);
+    assert_eq!(value, 0x5a5a_5a5a);
+    assert_eq!(*value.to_gray_in(..), 0x5a5a_5a5a.to_gray());
+    assert_eq!(*(-1i8).to_gray_in(4..), 0b1000_1111u8 as i8);
+    assert_eq!(*(-1i8).to_gray_in(6..6), -1);
+
+    for bits in 0..=8 {
+        let codes: Vec<u8> = GrayCodes::<u8>::new(bits).collect();
+        assert_eq!(codes.len(), 1 << bits);
+        assert!(codes.iter().all(|&code| (code as u16) >> bits == 0));
+        assert!(codes
+            .windows(2)
+            .all(|pair| (pair[0] ^ pair[1]).count_ones() == 1));
+        let mut sorted = codes.clone();
+        sorted.sort();
+        assert!(sorted.iter().cloned().eq(0..=((1u16 << bits) - 1) as u8));
+    }
+    assert!(GrayCodes::<i8>::new(8).eq(GrayCodes::<u8>::new(8).map(|code| code as i8)));
+    assert_eq!(GrayCodes::<u128>::new(0).collect::<Vec<_>>(), [0]);
+}
+
+#[test]
+fn test_submasks() {
+    use std::vec::Vec;
+    use Submasks;
+
+    for mask in 0..=u8::MAX {
+        let submasks: Vec<u8> = Submasks::new(mask).collect();
+        let expected: Vec<u8> = (0..=mask).rev().filter(|sub| sub & !mask == 0).collect();
+        assert_eq!(submasks, expected);
+        assert!(Submasks::new(mask as i8).eq(submasks.iter().map(|&sub| sub as i8)));
+    }
+
+    let mask = 0xf000_0000_0000_000fu64;
+    assert_eq!(Submasks::new(mask).count(), 256);
+    assert!(Submasks::new(mask).all(|sub| sub & !mask == 0));
+    assert_eq!(Submasks::new(u64::MAX).next(), Some(u64::MAX));
+    assert_eq!(Submasks::new(u128::MAX).nth(3), Some(u128::MAX - 3));
+    assert!(Submasks::new(0usize).eq([0]));
+}
+
+#[test]
+fn test_combinations() {
+    use std::vec::Vec;
+    use Combinations;
+
+    for n in 0..=8 {
+        for k in 0..=n {
+            let combinations: Vec<u8> = Combinations::<u8>::new(n, k).collect();
+            let expected: Vec<u8> = (0..=u8::MAX)
+                .filter(|value| (*value as u16) >> n == 0 && value.count_ones() as usize == k)
+                .collect();
+            assert_eq!(combinations, expected);
+            assert!(Combinations::<i8>::new(n, k).eq(combinations.iter().map(|&c| c as i8)));
+        }
+    }
+
+    let combinations: Vec<u64> = Combinations::<u64>::new(64, 63).collect();
+    assert_eq!(combinations.len(), 64);
+    assert_eq!(combinations[0], u64::MAX >> 1);
+    assert_eq!(combinations[63], u64::MAX << 1);
+    assert!(combinations.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(Combinations::<u128>::new(128, 128).eq([u128::MAX]));
+    assert!(Combinations::<i32>::new(32, 1).eq((0..32).map(|bit| 1i32 << bit)));
+    assert_eq!(Combinations::<u32>::new(32, 3).count(), 32 * 31 * 30 / 6);
+    assert!(Combinations::<u16>::new(16, 0).eq([0]));
+}
+
+#[test]
+#[should_panic]
+fn test_combinations_too_many_bits() {
+    use Combinations;
+
+    Combinations::<u8>::new(9, 1);
+}
+
+#[test]
+fn test_permute_bits() {
+    use core::num::Wrapping;
+    use BitPermutation;
+
+    let mut state = 0x0ddc_0ffe_e0dd_f00d;
+    for _ in 0..100 {
+        // a random permutation of the 64 bits and its inverse
+        let mut table = [0u8; 64];
+        for (bit, entry) in table.iter_mut().enumerate() {
+            *entry = bit as u8;
+        }
+        for i in (1..64).rev() {
+            table.swap(i, xorshift(&mut state) as usize % (i + 1));
+        }
+        let mut inverse = [0u8; 64];
+        for (bit, &source) in table.iter().enumerate() {
+            inverse[source as usize] = bit as u8;
+        }
+
+        let permutation = BitPermutation::<u64, u64>::new(&table);
+        let inverse = BitPermutation::<u64, u64>::new(&inverse);
+        for _ in 0..10 {
+            let value = xorshift(&mut state);
+            let permuted = permutation.apply(value);
+            assert!((0..64).all(|bit| permuted.get_bit(bit) == value.get_bit(table[bit] as usize)));
+            assert_eq!(value.permute_bits(&table), permuted);
+            assert_eq!(Wrapping(value).permute_bits(&table), Wrapping(permuted));
+            assert_eq!(inverse.apply(permuted), value);
+        }
+
+        // expansion from 32 to 48 bits and compression from 64 to 24 bits
+        let mut expansion = [0u8; 48];
+        for entry in expansion.iter_mut() {
+            *entry = (xorshift(&mut state) % 32) as u8;
+        }
+        let mut compression = [0u8; 24];
+        compression.copy_from_slice(&table[..24]);
+
+        let expand = BitPermutation::<u32, u64>::new(&expansion);
+        let compress = BitPermutation::<u64, u32>::new(&compression);
+        for _ in 0..10 {
+            let value = xorshift(&mut state);
+            let expanded = expand.apply(value as u32);
+            assert_eq!(expanded >> 48, 0);
+            assert!(
+                (0..48).all(|bit| expanded.get_bit(bit) == value.get_bit(expansion[bit] as usize))
+            );
+            let compressed = compress.apply(value);
+            assert_eq!(compressed as u64, value.permute_bits(&compression));
+        }
+    }
+
+    let reverse = BitPermutation::<i8, i16>::new(&[7, 6, 5, 4, 3, 2, 1, 0, 7, 7, 7, 7, 7, 7, 7, 7]);
+    assert_eq!(reverse.apply(i8::MIN), -0x0100 | 1);
+    assert_eq!(reverse.apply(0b0000_0110), 0b0110_0000);
+    assert_eq!(
+        BitPermutation::<u128, u8>::new(&[127, 0]).apply(1 << 127),
+        1
+    );
+    assert_eq!(BitPermutation::<u8, u8>::new(&[]).apply(u8::MAX), 0);
+    assert_eq!((-1i32).permute_bits(&[0; 31]), i32::MAX);
+}
+
+#[test]
+#[should_panic(expected = "permutation index is out of bounds of the input")]
+fn test_bit_permutation_index_out_of_bounds() {
+    use BitPermutation;
+
+    BitPermutation::<u16, u32>::new(&[0, 16]);
+}
+
+#[test]
+#[should_panic(expected = "permutation table is longer than the bit field")]
+fn test_permute_bits_table_too_long() {
+    0u8.permute_bits(&[0; 9]);
+}
+
+#[test]
+fn test_bit_matrix() {
+    use BitMatrix;
+
+    macro_rules! check_transpose {
+        ($state:expr, $($t:ty => $n:expr),*) => ($({
+            let mut matrix = BitMatrix([0 as $t; $n]);
+            for row in matrix.0.iter_mut() {
+                *row = xorshift($state) as $t;
+            }
+            if $n == 128 {
+                for row in matrix.0.iter_mut() {
+                    *row = *row << 64 | xorshift($state) as $t;
+                }
+            }
+
+            let transposed = matrix.transpose();
+            for i in 0..$n {
+                for j in 0..$n {
+                    assert_eq!(transposed.get(i, j), matrix.get(j, i));
+                }
+            }
+            assert_eq!(transposed.transpose(), matrix);
+            assert_eq!(BitMatrix::<$t, $n>::identity().transpose(), BitMatrix::identity());
+        })*)
+    }
+
+    let mut state = 0x9e37_79b9_7f4a_7c15;
+    check_transpose!(&mut state, u8 => 8, u16 => 16, u32 => 32, u64 => 64, u128 => 128);
+
+    // multiplication of a 3x8 and an 8x16 matrix against the definition of the product
+    for _ in 0..10 {
+        let mut a = BitMatrix([0u8; 3]);
+        for row in a.0.iter_mut() {
+            *row = xorshift(&mut state) as u8;
+        }
+        let mut b = BitMatrix([0u16; 8]);
+        for row in b.0.iter_mut() {
+            *row = xorshift(&mut state) as u16;
+        }
+
+        let product = a * b;
+        for i in 0..3 {
+            for j in 0..16 {
+                let entry = (0..8).filter(|&k| a.get(i, k) && b.get(k, j)).count() % 2 == 1;
+                assert_eq!(product.get(i, j), entry);
+            }
+        }
+        assert_eq!(a.multiply(&BitMatrix::<u8, 8>::identity()), a);
+    }
+
+    // (AB)^T = B^T A^T for square matrices
+    let mut a = BitMatrix([0u64; 64]);
+    let mut b = BitMatrix([0u64; 64]);
+    for (x, y) in a.0.iter_mut().zip(b.0.iter_mut()) {
+        *x = xorshift(&mut state);
+        *y = xorshift(&mut state);
+    }
+    assert_eq!((a * b).transpose(), b.transpose() * a.transpose());
+
+    // rows and columns
+    let mut matrix = BitMatrix([0u16; 4]);
+    matrix.set(0, 15, true).set(3, 0, true).set(2, 7, true);
+    assert!(matrix.rows().eq(&[0x8000, 0, 0x0080, 0x0001]));
+    assert!(matrix.column(0).eq([false, false, false, true]));
+    assert!(matrix.column(15).eq([true, false, false, false]));
+    assert_eq!(matrix.columns().len(), 16);
+    assert_eq!(
+        matrix
+            .columns()
+            .filter(|column| column.clone().any(|bit| bit))
+            .count(),
+        3
+    );
+    matrix.set(0, 15, false);
+    assert_eq!(matrix.0[0], 0);
+    assert!(BitMatrix([-1i32; 2]).get(1, 31));
+}
+
+#[test]
+#[should_panic]
+fn test_bit_matrix_column_out_of_bounds() {
+    use BitMatrix;
+
+    BitMatrix([0u8; 16]).get(0, 8);
+}
+
+#[test]
+#[should_panic(expected = "matrix dimensions do not match for multiplication")]
+fn test_bit_matrix_multiply_dimension_mismatch() {
+    use BitMatrix;
+
+    BitMatrix([0u8; 8]).multiply(&BitMatrix([0u8; 4]));
+}
+
+/// A bit array implementing only the required methods of `BitArray`, so that all other methods
+/// use their default implementations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MinimalBitArray<T, const N: usize>([T; N]);
+
+impl<T: BitField, const N: usize> BitArray<T> for MinimalBitArray<T, N> {
+    fn bit_length(&self) -> usize {
+        self.0.bit_length()
+    }
+
+    fn get_bit(&self, bit: usize) -> bool {
+        self.0.get_bit(bit)
+    }
+
+    fn get_bits<U: ::core::ops::RangeBounds<usize>>(&self, range: U) -> T {
+        self.0.get_bits(range)
+    }
+
+    fn set_bit(&mut self, bit: usize, value: bool) {
+        self.0.set_bit(bit, value);
+    }
+
+    fn set_bits<U: ::core::ops::RangeBounds<usize>>(&mut self, range: U, value: T) {
+        self.0.set_bits(range, value);
+    }
+}
+
+/// Checks that the default methods of `BitArray` agree with the methods of the slice
+/// implementation for the bit array `original`.
+fn check_default_bit_array_methods<T>(original: [T; 4])
+where
+    T: ::PermutationWord + Default + PartialEq + ::core::fmt::Debug,
+{
+    let bits = original.bit_length();
+    let minimal = MinimalBitArray(original);
+    let mut other = original;
+    other.rotate_left_bits(13);
+
+    for start in 0..=bits {
+        for end in start..=bits {
+            let len = end - start;
+            assert_eq!(
+                minimal.highest_set_bit_in(start..end),
+                original.highest_set_bit_in(start..end)
+            );
+            assert_eq!(
+                minimal.lowest_set_bit_in(start..end),
+                original.lowest_set_bit_in(start..end)
+            );
+            assert_eq!(
+                minimal.leading_zeros_in(start..end),
+                original.leading_zeros_in(start..end)
+            );
+            assert_eq!(
+                minimal.trailing_zeros_in(start..end),
+                original.trailing_zeros_in(start..end)
+            );
+            assert_eq!(
+                minimal.get_bits_as::<u32>(start..end),
+                original.get_bits_as::<u32>(start..end)
+            );
+
+            let (mut expected, mut actual) = (other, other);
+            original.get_bits_into(start..end, &mut expected);
+            minimal.get_bits_into(start..end, &mut actual);
+            assert_eq!(actual, expected);
+
+            let mut source = other;
+            source.shr_bits(bits - len);
+            let (mut expected, mut actual) = (original, minimal);
+            expected.set_bits_from(start..end, &source);
+            actual.set_bits_from(start..end, &source);
+            assert_eq!(actual.0, expected);
+
+            let value = other.get_bits_as::<u32>(..len);
+            let (mut expected, mut actual) = (original, minimal);
+            expected.set_bits_as(start..end, value);
+            actual.set_bits_as(start..end, value);
+            assert_eq!(actual.0, expected);
+
+            for value in [false, true] {
+                let (mut expected, mut actual) = (original, minimal);
+                expected.set_range_to(start..end, value);
+                actual.set_range_to(start..end, value);
+                assert_eq!(actual.0, expected);
+            }
+
+            for dest in 0..=bits - len {
+                let (mut expected, mut actual) = (original, minimal);
+                expected.copy_within_bits(start..end, dest);
+                actual.copy_within_bits(start..end, dest);
+                assert_eq!(actual.0, expected);
+            }
+
+            for n in -(len as isize) - 1..=len as isize + 1 {
+                let (mut expected, mut actual) = (original, minimal);
+                expected.shift_range(start..end, n);
+                actual.shift_range(start..end, n);
+                assert_eq!(actual.0, expected);
+            }
+
+            if len != 0 && (end - 1) / T::BIT_LENGTH == start / T::BIT_LENGTH {
+                assert_eq!(
+                    minimal.get_bits_masked(start..end),
+                    original.get_bits_masked(start..end)
+                );
+
+                let value = other.get_bits_masked(start..end);
+                let (mut expected, mut actual) = (original, minimal);
+                expected.set_bits_masked(start..end, value);
+                actual.set_bits_masked(start..end, value);
+                assert_eq!(actual.0, expected);
+            }
+        }
+    }
+
+    for n in 0..2 * bits {
+        let (mut expected, mut actual) = (original, minimal);
+        expected.shl_bits(n);
+        actual.shl_bits(n);
+        assert_eq!(actual.0, expected);
+
+        let (mut expected, mut actual) = (original, minimal);
+        expected.shr_bits(n);
+        actual.shr_bits(n);
+        assert_eq!(actual.0, expected);
+
+        let (mut expected, mut actual) = (original, minimal);
+        expected.rotate_left_bits(n);
+        actual.rotate_left_bits(n);
+        assert_eq!(actual.0, expected);
+
+        let (mut expected, mut actual) = (original, minimal);
+        expected.rotate_right_bits(n);
+        actual.rotate_right_bits(n);
+        assert_eq!(actual.0, expected);
+    }
+
+    let ranges = [1..3, 9..14, 20..21];
+    assert_eq!(
+        minimal.get_split_bits(&ranges),
+        original.get_split_bits(&ranges)
+    );
+    let value = other.get_split_bits(&[0..4, 4..8]);
+    let (mut expected, mut actual) = (original, minimal);
+    expected.set_split_bits(&[3..5, 26..32], value.get_bits_masked(..8));
+    actual.set_split_bits(&[3..5, 26..32], value.get_bits_masked(..8));
+    assert_eq!(actual.0, expected);
+
+    unsafe {
+        assert_eq!(minimal.get_bit_unchecked(9), original.get_bit_unchecked(9));
+        assert_eq!(
+            minimal.get_bits_unchecked(5..11),
+            original.get_bits_unchecked(5..11)
+        );
+        let (mut expected, mut actual) = (original, minimal);
+        expected.set_bit_unchecked(17, true);
+        actual.set_bit_unchecked(17, true);
+        expected.set_bits_unchecked(2..5, original.get_bits_unchecked(0..3));
+        actual.set_bits_unchecked(2..5, original.get_bits_unchecked(0..3));
+        assert_eq!(actual.0, expected);
+    }
+}
+
+#[test]
+fn test_default_bit_array_methods() {
+    check_default_bit_array_methods([0xa5u8, 0x3c, 0xff, 0x70]);
+    check_default_bit_array_methods([-0x5bi8, 0x3c, -0x01, 0x70]);
+}
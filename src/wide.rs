@@ -0,0 +1,95 @@
+use core::ops::RangeBounds;
+
+use {to_regular_range, BitArray, BitField};
+
+/// A fixed-size array of bit fields that behaves like a single wide integer.
+///
+/// Element 0 holds the least significant bits, so `Wide<u64, 4>` is a 256-bit bit field whose
+/// bit `i` is bit `i % 64` of element `i / 64`. Unlike `BitArray`, ranges passed to `get_bits`
+/// and `set_bits` may span any number of elements.
+///
+/// ```rust
+/// use bit_field::{BitField, Wide};
+///
+/// let mut mask = Wide([0u64; 4]);
+/// assert_eq!(Wide::<u64, 4>::BIT_LENGTH, 256);
+///
+/// mask.set_bits(60..132, Wide([u64::MAX, 0xff, 0, 0]));
+/// assert_eq!(mask, Wide([0xf000_0000_0000_0000, u64::MAX, 0xf, 0]));
+/// assert_eq!(mask.get_bits(64..), Wide([u64::MAX, 0xf, 0, 0]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Wide<T, const N: usize>(pub [T; N]);
+
+impl<T: Copy + Default, const N: usize> Default for Wide<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Wide([T::default(); N])
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Wide<T, N> {
+    #[inline]
+    fn from(array: [T; N]) -> Self {
+        Wide(array)
+    }
+}
+
+impl<T: BitField + Copy + Default + PartialEq, const N: usize> BitField for Wide<T, N> {
+    const BIT_LENGTH: usize = N * T::BIT_LENGTH;
+
+    #[track_caller]
+    #[inline]
+    fn get_bit(&self, bit: usize) -> bool {
+        assert!(bit < Self::BIT_LENGTH);
+
+        self.0[..].get_bit(bit)
+    }
+
+    #[track_caller]
+    #[inline]
+    fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> Self {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+
+        let mut bits = Self::default();
+        self.0.get_bits_into(range, &mut bits.0);
+        bits
+    }
+
+    #[track_caller]
+    #[inline]
+    fn set_bit(&mut self, bit: usize, value: bool) -> &mut Self {
+        assert!(bit < Self::BIT_LENGTH);
+
+        self.0[..].set_bit(bit, value);
+        self
+    }
+
+    #[track_caller]
+    #[inline]
+    fn set_bits<U: RangeBounds<usize>>(&mut self, range: U, value: Self) -> &mut Self {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < Self::BIT_LENGTH);
+        assert!(range.end <= Self::BIT_LENGTH);
+        assert!(range.start <= range.end);
+
+        let len = range.len();
+        assert!(
+            value.0.iter().enumerate().all(|(i, word)| {
+                let start = i * T::BIT_LENGTH;
+                start + T::BIT_LENGTH <= len
+                    || *word == T::default()
+                    || start < len && word.get_bits(len - start..) == T::default()
+            }),
+            "value does not fit into bit range"
+        );
+
+        self.0.set_bits_from(range, &value.0);
+        self
+    }
+}